@@ -0,0 +1,91 @@
+//! Benchmarks comparing the exact and approximate
+//! conversions. Run with `cargo bench`, optionally with
+//! `--features table-lookup` to measure that backend.
+
+use std::hint::black_box;
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use keytones::*;
+
+/// A single-key conversion under test.
+type Conversion = fn(u8) -> f32;
+
+/// Keys of a standard 88-key piano, as a realistic mix.
+fn piano_keys() -> Vec<u8> {
+    (21..=108).collect()
+}
+
+/// A long pseudo-random key sequence, too large to stay in
+/// cache, so that lookups cannot be predicted or prefetched.
+fn random_keys(n: usize) -> Vec<u8> {
+    let mut state: u32 = 0x2545_f491;
+    (0..n)
+        .map(|_| {
+            // xorshift32
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state % 128) as u8
+        })
+        .collect()
+}
+
+fn bench_single(c: &mut Criterion) {
+    let keys = piano_keys();
+    let fns: [(&str, Conversion); 4] = [
+        ("key_to_frequency_raw", key_to_frequency_raw),
+        ("key_to_frequency_approx_raw", key_to_frequency_approx_raw),
+        ("key_to_period_raw", key_to_period_raw),
+        ("key_to_period_approx_raw", key_to_period_approx_raw),
+    ];
+    let mut group = c.benchmark_group("piano");
+    group.throughput(Throughput::Elements(keys.len() as u64));
+    for (name, f) in fns {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for &key in &keys {
+                    black_box(f(black_box(key)));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_batch(c: &mut Criterion) {
+    let keys: Vec<u8> = (0..1024).map(|k| (k % 128) as u8).collect();
+    let mut out = vec![0.0; keys.len()];
+    let mut group = c.benchmark_group("batch");
+    group.throughput(Throughput::Elements(keys.len() as u64));
+    group.bench_function("batch_key_to_frequency", |b| {
+        b.iter(|| batch_key_to_frequency(black_box(&keys), black_box(&mut out)))
+    });
+    group.bench_function("batch_key_to_period", |b| {
+        b.iter(|| batch_key_to_period(black_box(&keys), black_box(&mut out)))
+    });
+    group.finish();
+}
+
+fn bench_random(c: &mut Criterion) {
+    let keys = random_keys(1 << 22);
+    let fns: [(&str, Conversion); 2] = [
+        ("key_to_frequency_raw", key_to_frequency_raw),
+        ("key_to_frequency_approx_raw", key_to_frequency_approx_raw),
+    ];
+    let mut group = c.benchmark_group("random");
+    group.throughput(Throughput::Elements(keys.len() as u64));
+    group.sample_size(10);
+    for (name, f) in fns {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for &key in &keys {
+                    black_box(f(black_box(key)));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_single, bench_batch, bench_random);
+criterion_main!(benches);
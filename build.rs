@@ -1,29 +1,228 @@
-//! We generate some Rust code in `build.rs` containing
-//! needed constants for the library.
+//! The constants needed by the library are checked in as
+//! `src/consts.rs`, so that normal builds need no build-time
+//! computation. With the `regen-consts` feature, `build.rs`
+//! regenerates them and fails the build if they differ from
+//! the checked-in copy.
 
+#[cfg(feature = "regen-consts")]
 use std::{env, fs, io, path::Path};
 
+#[cfg(feature = "regen-consts")]
 use microcheby::ChebyshevExpansion as C;
 
+#[cfg(feature = "regen-consts")]
+/// Fit an `N`-term Chebyshev series to `f` on
+/// `[x_min, x_max]` in double precision. The returned
+/// coefficients have the zeroth term pre-halved, matching
+/// the `microcheby` internal representation.
+fn fit_f64<const N: usize, F: Fn(f64) -> f64>(x_min: f64, x_max: f64, f: F) -> [f64; N] {
+    use std::f64::consts::PI;
+
+    let mut coeffs = [0.0; N];
+    let n_inv = 1.0 / (N as f64);
+    for (j, c_j) in coeffs.iter_mut().enumerate() {
+        for k in 0..N {
+            let x_rel = 0.5 * (1.0 + f64::cos(PI * (k as f64 + 0.5) * n_inv));
+            let x = x_min + (x_max - x_min) * x_rel;
+            let weight = f64::cos(PI * (j as f64) * (k as f64 + 0.5) * n_inv);
+            *c_j += 2.0 * f(x) * weight * n_inv;
+        }
+    }
+    coeffs[0] *= 0.5;
+    coeffs
+}
+
+#[cfg(feature = "regen-consts")]
 fn write_consts<F: io::Write>(mut out: F) {
     let f = |n| 440.0 * f32::powf(2.0, (n + 116.0 - 69.0) / 12.0);
     let mut coeffs: [f32; 4] = C::fit(0.0, 11.0, f).coeffs();
     coeffs[0] *= 0.5;
-    let cst = format!("pub const CHEBYSHEV_TOP_OCTAVE: [f32; 4] = {coeffs:?};");
+    let cst = format!("pub const CHEBYSHEV_TOP_OCTAVE: [f32; CHEBYSHEV_ORDER] = {coeffs:?};");
 
     let p = |n| 1.0 / (440.0 * f32::powf(2.0, (n - 69.0) / 12.0));
     let mut coeffs: [f32; 4] = C::fit(0.0, 11.0, p).coeffs();
     coeffs[0] *= 0.5;
-    let csb = format!("pub const CHEBYSHEV_BOTTOM_OCTAVE: [f32; 4] = {coeffs:?};");
+    let csb = format!("pub const CHEBYSHEV_BOTTOM_OCTAVE: [f32; CHEBYSHEV_ORDER] = {coeffs:?};");
+
+    let r = |n| f32::powf(2.0, n / 12.0);
+    let mut coeffs: [f32; 5] = C::fit(0.0, 12.0, r).coeffs();
+    coeffs[0] *= 0.5;
+    let cso = format!("pub const CHEBYSHEV_OCTAVE: [f32; 5] = {coeffs:?};");
+
+    let l = |x| 69.0 + 12.0 * f32::log2(x / 440.0);
+    let mut coeffs: [f32; 6] = C::fit(1.0, 2.0, l).coeffs();
+    coeffs[0] *= 0.5;
+    let csl = format!("pub const CHEBYSHEV_LOG_OCTAVE: [f32; 6] = {coeffs:?};");
+
+    let f = |n| 440.0 * f64::powf(2.0, (n + 116.0 - 69.0) / 12.0);
+    let coeffs: [f64; 10] = fit_f64(0.0, 11.0, f);
+    let cst64 = format!("pub const CHEBYSHEV_TOP_OCTAVE_F64: [f64; 10] = {coeffs:?};");
+
+    let p = |n| 1.0 / (440.0 * f64::powf(2.0, (n - 69.0) / 12.0));
+    let coeffs: [f64; 10] = fit_f64(0.0, 11.0, p);
+    let csb64 = format!("pub const CHEBYSHEV_BOTTOM_OCTAVE_F64: [f64; 10] = {coeffs:?};");
+
+    let f = |n| 440.0 * f32::powf(2.0, (n + 116.0 - 69.0) / 12.0);
+    let mut coeffs: [f32; 3] = C::fit(0.0, 11.0, f).coeffs();
+    coeffs[0] *= 0.5;
+    let cst3 = format!("pub const CHEBYSHEV_TOP_OCTAVE_3: [f32; 3] = {coeffs:?};");
+
+    let mut coeffs: [f32; 6] = C::fit(0.0, 11.0, f).coeffs();
+    coeffs[0] *= 0.5;
+    let cst6 = format!("pub const CHEBYSHEV_TOP_OCTAVE_6: [f32; 6] = {coeffs:?};");
+
+    let f = |n| 440.0 * f64::powf(2.0, (n + 116.0 - 69.0) / 12.0);
+    let coeffs: [f64; 8] = fit_f64(0.0, 11.0, f);
+    let coeffs = coeffs.map(|c| c as f32);
+    let csth = format!("pub const CHEBYSHEV_TOP_OCTAVE_HI: [f32; 8] = {coeffs:?};");
+
+    let p = |n| 1.0 / (440.0 * f64::powf(2.0, (n - 69.0) / 12.0));
+    let coeffs: [f64; 8] = fit_f64(0.0, 11.0, p);
+    let coeffs = coeffs.map(|c| c as f32);
+    let csbh = format!("pub const CHEBYSHEV_BOTTOM_OCTAVE_HI: [f32; 8] = {coeffs:?};");
+
+    // Use the same f32 formula as `key_to_frequency`, so that
+    // the table entries match it exactly.
+    let f = |k: usize| 440.0 * f32::powf(2.0, (k as f32 - 69.0) / 12.0);
+    let freqs: [f32; 128] = std::array::from_fn(f);
+    let ft = format!("pub const FREQUENCY_TABLE: [f32; 128] = {freqs:?};");
+
+    let periods: [f32; 128] = std::array::from_fn(|k| 1.0 / f(k));
+    let pt = format!("pub const PERIOD_TABLE: [f32; 128] = {periods:?};");
+
+    let hz: [u16; 128] = std::array::from_fn(|k| f(k).round() as u16);
+    let fhz = format!("pub static FREQUENCY_HZ_TABLE: [u16; 128] = {hz:?};");
+
+    let f = |k: usize| 440.0 * f64::powf(2.0, (k as f64 + 116.0 - 69.0) / 12.0);
+    let freqs: [u32; 12] = std::array::from_fn(|k| (f(k) * 65536.0).round() as u32);
+    let fq16 = format!("pub const FREQUENCY_Q16_TOP_OCTAVE: [u32; 12] = {freqs:?};");
+
+    let p = |k: usize| 1.0 / (440.0 * f64::powf(2.0, (k as f64 - 69.0) / 12.0));
+    let periods: [u32; 12] = std::array::from_fn(|k| (p(k) * 16777216.0).round() as u32);
+    let pq24 = format!("pub const PERIOD_Q24_BOTTOM_OCTAVE: [u32; 12] = {periods:?};");
+
+    let note_names = |pitch_classes: [&str; 12]| -> [String; 128] {
+        std::array::from_fn(|k| format!("{}{}", pitch_classes[k % 12], k as i32 / 12 - 1))
+    };
+    let sharps = note_names([
+        "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+    ]);
+    let nns = format!("pub const NOTE_NAMES_SHARP: [&str; 128] = {sharps:?};");
+    let flats = note_names([
+        "C", "Db", "D", "Eb", "E", "F", "Gb", "G", "Ab", "A", "Bb", "B",
+    ]);
+    let nnf = format!("pub const NOTE_NAMES_FLAT: [&str; 128] = {flats:?};");
 
     let lines = [
-        "// AUTOGENERATED by build.rs. Edits will be overwritten!",
+        "// GENERATED by build.rs: regenerate with the regen-consts feature.",
         "",
-        "/// Chebyshev series constants for top octave frequencies.",
+        "/// Number of coefficients in [CHEBYSHEV_TOP_OCTAVE] and",
+        "/// [CHEBYSHEV_BOTTOM_OCTAVE].",
+        "pub const CHEBYSHEV_ORDER: usize = 4;",
+        "",
+        "/// Chebyshev series coefficients for top octave frequencies,",
+        "/// as used by [key_to_frequency_approx](crate::key_to_frequency_approx).",
+        "///",
+        "/// The series approximates the frequency in Hz of key",
+        "/// $116 + x$, $440 \\cdot 2^{(x + 47) / 12}$, over the domain",
+        "/// $0 \\le x \\le 11$. Frequencies of lower keys are found",
+        "/// by halving once per octave. The maximum error is less",
+        "/// than 0.12¢.",
+        "///",
+        "/// The coefficients are for the Chebyshev polynomials",
+        "/// $T_0 \\ldots T_3$ of $t = 2x / 11 - 1$. The $T_0$",
+        "/// coefficient is stored already halved, so that",
+        "/// $f(x) = c_0 + \\sum_{i \\ge 1} c_i T_i(t)$.",
         &cst,
         "",
-        "/// Chebyshev series constants for bottom octave periods.",
+        "/// Chebyshev series coefficients for bottom octave periods,",
+        "/// as used by [key_to_period_approx](crate::key_to_period_approx).",
+        "///",
+        "/// The series approximates the unit period in seconds of",
+        "/// key $x$, $1 / (440 \\cdot 2^{(x - 69) / 12})$, over the",
+        "/// domain $0 \\le x \\le 11$. Periods of higher keys are",
+        "/// found by halving once per octave. The maximum error is",
+        "/// less than 0.12¢.",
+        "///",
+        "/// The coefficients are laid out as for",
+        "/// [CHEBYSHEV_TOP_OCTAVE].",
         &csb,
+        "",
+        "/// Chebyshev series constants for frequency ratios of",
+        "/// fractional semitones in a full octave.",
+        &cso,
+        "",
+        "/// Chebyshev series constants for fractional keys of",
+        "/// frequencies in a single octave from 1 to 2 Hz.",
+        &csl,
+        "",
+        "/// Double-precision Chebyshev series constants for top",
+        "/// octave frequencies.",
+        &cst64,
+        "",
+        "/// Double-precision Chebyshev series constants for bottom",
+        "/// octave periods.",
+        &csb64,
+        "",
+        "/// Order-3 Chebyshev series constants for top octave",
+        "/// frequencies.",
+        &cst3,
+        "",
+        "/// Order-6 Chebyshev series constants for top octave",
+        "/// frequencies.",
+        &cst6,
+        "",
+        "/// Higher-order Chebyshev series constants for top octave",
+        "/// frequencies.",
+        &csth,
+        "",
+        "/// Higher-order Chebyshev series constants for bottom",
+        "/// octave periods.",
+        &csbh,
+        "",
+        "/// Frequency in Hz of each midi key, indexed by key. The",
+        "/// entries are identical to the results of",
+        "/// [key_to_frequency](crate::key_to_frequency).",
+        "///",
+        "/// ```",
+        "/// # use keytones::FREQUENCY_TABLE;",
+        "/// assert_eq!(FREQUENCY_TABLE[69], 440.0);",
+        "/// ```",
+        &ft,
+        "",
+        "/// Unit period in seconds of each midi key, indexed by key.",
+        "/// The entries are identical to the results of",
+        "/// [key_to_period](crate::key_to_period).",
+        "///",
+        "/// ```",
+        "/// # use keytones::PERIOD_TABLE;",
+        "/// assert_eq!(PERIOD_TABLE[69], 1.0 / 440.0);",
+        "/// ```",
+        &pt,
+        "",
+        "/// Frequency of each midi key rounded to the nearest whole",
+        "/// Hz, indexed by key. The entries are identical to the",
+        "/// rounded results of [key_to_frequency](crate::key_to_frequency).",
+        "///",
+        "/// ```",
+        "/// # use keytones::FREQUENCY_HZ_TABLE;",
+        "/// assert_eq!(FREQUENCY_HZ_TABLE[60], 262);",
+        "/// ```",
+        &fhz,
+        "",
+        "/// Q16.16 fixed-point frequencies of the top octave keys.",
+        "#[cfg(feature = \"fixed-point\")]",
+        &fq16,
+        "",
+        "/// Q8.24 fixed-point periods of the bottom octave keys.",
+        "#[cfg(feature = \"fixed-point\")]",
+        &pq24,
+        "",
+        "/// Note name of each midi key with sharp spelling.",
+        &nns,
+        "",
+        "/// Note name of each midi key with flat spelling.",
+        &nnf,
     ];
 
     for line in lines {
@@ -31,10 +230,41 @@ fn write_consts<F: io::Write>(mut out: F) {
     }
 }
 
-fn main() {
+/// Regenerate the constants into `OUT_DIR` and compare them
+/// with the checked-in `src/consts.rs`.
+#[cfg(feature = "regen-consts")]
+fn check_consts() {
+    let mut generated = Vec::new();
+    write_consts(&mut generated);
+    let generated = String::from_utf8(generated).unwrap();
+
     let out_dir = env::var("OUT_DIR").unwrap();
     let dest_path = Path::new(&out_dir).join("consts.rs");
-    let f = fs::File::create(dest_path).unwrap();
-    write_consts(f);
+    fs::write(&dest_path, &generated).unwrap();
+
+    let committed = fs::read_to_string("src/consts.rs").unwrap();
+    if committed != generated {
+        let mut diff = String::new();
+        for (old, new) in committed.lines().zip(generated.lines()) {
+            if old != new {
+                diff += &format!("- {old}\n+ {new}\n");
+            }
+        }
+        if committed.lines().count() != generated.lines().count() {
+            diff += "(line counts differ)\n";
+        }
+        panic!(
+            "src/consts.rs is out of date; regenerated copy is in {}\n{diff}",
+            dest_path.display(),
+        );
+    }
+}
+
+fn main() {
+    #[cfg(feature = "regen-consts")]
+    {
+        check_consts();
+        println!("cargo::rerun-if-changed=src/consts.rs");
+    }
     println!("cargo::rerun-if-changed=build.rs");
 }
@@ -0,0 +1,109 @@
+//! Report the worst-case and RMS error in cents of each
+//! approximate conversion over all 128 keys, measured
+//! against a double-precision reference. Exits with failure
+//! if any worst case exceeds the bound given in the docs.
+//!
+//! Run with `cargo run --example accuracy`.
+
+use std::process::ExitCode;
+
+use keytones::*;
+
+/// Cents corresponding to a relative error of `r`.
+fn relative_to_cents(r: f64) -> f64 {
+    1200.0 * f64::log2(1.0 + r)
+}
+
+/// A conversion under test: its name, its error in cents
+/// at a key, and its documented worst-case error in cents.
+struct Approx {
+    name: &'static str,
+    cents: fn(u8) -> f64,
+    bound: f64,
+    bound_doc: &'static str,
+}
+
+fn frequency_cents(f: f64, key: u8) -> f64 {
+    1200.0 * f64::log2(f / key_to_frequency_f64(key))
+}
+
+fn period_cents(p: f64, key: u8) -> f64 {
+    1200.0 * f64::log2(key_to_period_f64(key) / p)
+}
+
+fn main() -> ExitCode {
+    let approxes = [
+        Approx {
+            name: "key_to_frequency_approx_raw",
+            cents: |k| frequency_cents(key_to_frequency_approx_raw(k) as f64, k),
+            bound: 0.12,
+            bound_doc: "0.12¢",
+        },
+        Approx {
+            name: "key_to_frequency_approx_hi",
+            cents: |k| frequency_cents(key_to_frequency_approx_hi(k) as f64, k),
+            bound: 0.000125,
+            bound_doc: "~0.00012¢",
+        },
+        Approx {
+            name: "key_to_frequency_approx_f64",
+            cents: |k| frequency_cents(key_to_frequency_approx_f64(k), k),
+            bound: relative_to_cents(1.0e-13),
+            bound_doc: "1e-13",
+        },
+        Approx {
+            name: "key_to_period_approx_raw",
+            cents: |k| period_cents(key_to_period_approx_raw(k) as f64, k),
+            bound: 0.12,
+            bound_doc: "0.12¢",
+        },
+        Approx {
+            name: "key_to_period_approx_hi",
+            cents: |k| period_cents(key_to_period_approx_hi(k) as f64, k),
+            bound: 0.000065,
+            bound_doc: "~0.00006¢",
+        },
+        Approx {
+            name: "key_to_period_approx_f64",
+            cents: |k| period_cents(key_to_period_approx_f64(k), k),
+            bound: relative_to_cents(1.0e-13),
+            bound_doc: "1e-13",
+        },
+    ];
+
+    let mut ok = true;
+    println!(
+        "{:<28} {:>12} {:>5} {:>12} {:>12} {:>10}",
+        "function", "max ¢", "key", "rms ¢", "bound ¢", "(as doc)",
+    );
+    for a in &approxes {
+        let mut max: f64 = 0.0;
+        let mut worst_key = 0;
+        let mut sum_sq = 0.0;
+        for key in 0..=127 {
+            let c = (a.cents)(key);
+            if c.abs() > max {
+                max = c.abs();
+                worst_key = key;
+            }
+            sum_sq += c * c;
+        }
+        let rms = f64::sqrt(sum_sq / 128.0);
+        let flag = if max > a.bound {
+            ok = false;
+            "  EXCEEDED"
+        } else {
+            ""
+        };
+        println!(
+            "{:<28} {:>12.7} {:>5} {:>12.7} {:>12.7} {:>10}{}",
+            a.name, max, worst_key, rms, a.bound, a.bound_doc, flag,
+        );
+    }
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,63 @@
+//! Conversion of many keys at once.
+
+use crate::{key_to_frequency_raw, key_to_period_raw};
+
+/// Computes the frequency of each midi key in `keys`,
+/// storing it at the same index in `out`. See
+/// [key_to_frequency_raw].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::batch_key_to_frequency;
+/// let mut out = [0.0; 2];
+/// batch_key_to_frequency(&[57, 69], &mut out);
+/// assert_eq!(out, [220.0, 440.0]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `keys` and `out` are of different lengths, or
+/// if any key is not in the range `0..=127`.
+pub fn batch_key_to_frequency(keys: &[u8], out: &mut [f32]) {
+    assert_eq!(keys.len(), out.len(), "batch length mismatch");
+    for (&key, f) in keys.iter().zip(out) {
+        *f = key_to_frequency_raw(key);
+    }
+}
+
+/// Computes the unit period of each midi key in `keys`,
+/// storing it at the same index in `out`. See
+/// [key_to_period_raw].
+///
+/// # Panics
+///
+/// Panics if `keys` and `out` are of different lengths, or
+/// if any key is not in the range `0..=127`.
+pub fn batch_key_to_period(keys: &[u8], out: &mut [f32]) {
+    assert_eq!(keys.len(), out.len(), "batch length mismatch");
+    for (&key, p) in keys.iter().zip(out) {
+        *p = key_to_period_raw(key);
+    }
+}
+
+#[test]
+fn test_batch() {
+    let keys: [u8; 128] = core::array::from_fn(|k| 127 - k as u8);
+    let mut freqs = [0.0; 128];
+    let mut periods = [0.0; 128];
+    batch_key_to_frequency(&keys, &mut freqs);
+    batch_key_to_period(&keys, &mut periods);
+    for i in 0..128 {
+        assert_eq!(freqs[i], key_to_frequency_raw(keys[i]));
+        assert_eq!(periods[i], key_to_period_raw(keys[i]));
+    }
+
+    batch_key_to_frequency(&[], &mut []);
+}
+
+#[test]
+#[should_panic(expected = "batch length mismatch")]
+fn test_batch_mismatch() {
+    batch_key_to_frequency(&[60, 69], &mut [0.0; 3]);
+}
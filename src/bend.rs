@@ -0,0 +1,328 @@
+//! MIDI pitch bend.
+
+use crate::{check_key, key_to_frequency_fractional, key_to_frequency_fractional_approx, or_panic};
+
+/// The conventional default pitch bend range, in semitones
+/// either side of the key.
+pub const DEFAULT_BEND_RANGE: f32 = 2.0;
+
+/// The MPE default pitch bend range of member (per-note)
+/// channels, in semitones either side of the key.
+pub const MPE_NOTE_BEND_RANGE: f32 = 48.0;
+
+/// Center (no bend) value of a 14-bit MIDI pitch bend.
+pub const BEND_CENTER: u16 = 8192;
+
+/// Converts a 14-bit MIDI pitch bend value to an offset in
+/// semitones. The bend range is asymmetric in raw values:
+/// there are 8192 values below center but only 8191 above,
+/// so each side is scaled separately so that 0 gives
+/// exactly `-range` and 16383 gives exactly `range`.
+fn bend_to_semitones(bend: u16, range: f32) -> f32 {
+    assert!(bend < 16384, "pitch bend {} out of range 0..=16383", bend);
+    assert!(
+        range.is_finite(),
+        "pitch bend range {} is not finite",
+        range
+    );
+    let offset = bend as f32 - BEND_CENTER as f32;
+    if bend < BEND_CENTER {
+        offset * range / 8192.0
+    } else {
+        offset * range / 8191.0
+    }
+}
+
+/// Fractional key for a key bent by the given number of
+/// semitones, clamped to the range of midi keys.
+fn bent_key_semitones(key: u8, semitones: f32) -> f32 {
+    let key = or_panic(check_key(key));
+    (key as f32 + semitones).clamp(0.0, 127.0)
+}
+
+/// Fractional key for a bent key, clamped to the range of
+/// midi keys.
+fn bent_key(key: u8, bend: u16, range: f32) -> f32 {
+    bent_key_semitones(key, bend_to_semitones(bend, range))
+}
+
+/// Computes the frequency of the given midi key under the
+/// given 14-bit pitch bend, where `bend` ranges from 0
+/// (down `bend_range` semitones) through [BEND_CENTER] (no
+/// bend) to 16383 (up `bend_range` semitones).
+///
+/// If the bent pitch lies outside the range of midi keys it
+/// is clamped to the frequency of key 0 or key 127.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_bend_to_frequency;
+/// assert_eq!(key_bend_to_frequency(69, 8192, 2.0), 440.0);
+/// assert_eq!(key_bend_to_frequency(69, 16383, 12.0), 880.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`, `bend`
+/// is not in the range `0..=16383`, or `bend_range` is not
+/// finite.
+pub fn key_bend_to_frequency(key: u8, bend: u16, bend_range: f32) -> f32 {
+    key_to_frequency_fractional(bent_key(key, bend, bend_range))
+}
+
+/// Like [key_bend_to_frequency], but returns `None` rather
+/// than panicking if `key` is not in the range `0..=127` or
+/// `bend` is not in the range `0..=16383`. Also returns
+/// `None` if `bend_range` is not finite.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_bend_to_frequency_checked;
+/// assert_eq!(key_bend_to_frequency_checked(69, 8192, 2.0), Some(440.0));
+/// assert_eq!(key_bend_to_frequency_checked(69, 16384, 2.0), None);
+/// assert_eq!(key_bend_to_frequency_checked(128, 8192, 2.0), None);
+/// assert_eq!(key_bend_to_frequency_checked(69, 8192, f32::NAN), None);
+/// ```
+pub fn key_bend_to_frequency_checked(key: u8, bend: u16, bend_range: f32) -> Option<f32> {
+    if check_key(key).is_err() || bend >= 16384 || !bend_range.is_finite() {
+        return None;
+    }
+    Some(key_bend_to_frequency(key, bend, bend_range))
+}
+
+/// Like [key_bend_to_frequency], with the conventional
+/// [DEFAULT_BEND_RANGE] of ±2 semitones.
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127` or `bend`
+/// is not in the range `0..=16383`.
+pub fn key_bend_to_frequency_default(key: u8, bend: u16) -> f32 {
+    key_bend_to_frequency(key, bend, DEFAULT_BEND_RANGE)
+}
+
+/// Computes the approximate frequency of the given midi key
+/// under the given 14-bit pitch bend, as
+/// [key_bend_to_frequency] but using
+/// [key_to_frequency_fractional_approx], which avoids
+/// `powf`.
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`, `bend`
+/// is not in the range `0..=16383`, or `bend_range` is not
+/// finite.
+pub fn key_bend_to_frequency_approx(key: u8, bend: u16, bend_range: f32) -> f32 {
+    key_to_frequency_fractional_approx(bent_key(key, bend, bend_range))
+}
+
+/// The pitch of a note under MIDI Polyphonic Expression
+/// (MPE), where the note's own member channel pitch bend and
+/// the zone's manager channel pitch bend are both applied.
+/// The two bends are added in semitones.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::MpePitch;
+/// let mut pitch = MpePitch::new(69);
+/// assert_eq!(pitch.frequency(), 440.0);
+/// // Down 12 semitones on the member channel.
+/// pitch.note_bend = 6144;
+/// assert_eq!(pitch.frequency(), 220.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MpePitch {
+    /// Midi key of the note.
+    pub key: u8,
+    /// 14-bit pitch bend of the note's member channel.
+    pub note_bend: u16,
+    /// Bend range of the member channel in semitones.
+    pub note_bend_range: f32,
+    /// 14-bit pitch bend of the zone's manager channel.
+    pub channel_bend: u16,
+    /// Bend range of the manager channel in semitones.
+    pub channel_bend_range: f32,
+}
+
+impl MpePitch {
+    /// Make an unbent pitch for the given key, with the MPE
+    /// default bend ranges: [MPE_NOTE_BEND_RANGE] for the
+    /// member channel and [DEFAULT_BEND_RANGE] for the manager
+    /// channel.
+    pub const fn new(key: u8) -> Self {
+        MpePitch {
+            key,
+            note_bend: BEND_CENTER,
+            note_bend_range: MPE_NOTE_BEND_RANGE,
+            channel_bend: BEND_CENTER,
+            channel_bend_range: DEFAULT_BEND_RANGE,
+        }
+    }
+
+    /// Total bend in semitones.
+    fn semitones(&self) -> f32 {
+        bend_to_semitones(self.note_bend, self.note_bend_range)
+            + bend_to_semitones(self.channel_bend, self.channel_bend_range)
+    }
+
+    /// Frequency of this pitch. If the bent pitch lies outside
+    /// the range of midi keys it is clamped to the frequency
+    /// of key 0 or key 127.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not in the range `0..=127`,
+    /// either bend is not in the range `0..=16383`, or either
+    /// bend range is not finite.
+    pub fn frequency(&self) -> f32 {
+        key_to_frequency_fractional(bent_key_semitones(self.key, self.semitones()))
+    }
+
+    /// Approximate frequency of this pitch, as
+    /// [MpePitch::frequency] but using
+    /// [key_to_frequency_fractional_approx].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not in the range `0..=127`,
+    /// either bend is not in the range `0..=16383`, or either
+    /// bend range is not finite.
+    pub fn frequency_approx(&self) -> f32 {
+        key_to_frequency_fractional_approx(bent_key_semitones(self.key, self.semitones()))
+    }
+}
+
+#[test]
+fn test_bend_to_semitones() {
+    assert_eq!(bend_to_semitones(0, 2.0), -2.0);
+    assert_eq!(bend_to_semitones(4096, 2.0), -1.0);
+    assert_eq!(bend_to_semitones(8192, 2.0), 0.0);
+    assert_eq!(bend_to_semitones(16383, 2.0), 2.0);
+    assert_eq!(bend_to_semitones(16383, 48.0), 48.0);
+}
+
+#[test]
+#[should_panic(expected = "pitch bend range NaN is not finite")]
+fn test_key_bend_nan_range() {
+    key_bend_to_frequency(69, 8192, f32::NAN);
+}
+
+#[test]
+#[should_panic(expected = "pitch bend range -inf is not finite")]
+fn test_key_bend_approx_infinite_range() {
+    key_bend_to_frequency_approx(69, 8192, f32::NEG_INFINITY);
+}
+
+#[test]
+#[should_panic(expected = "pitch bend range NaN is not finite")]
+fn test_mpe_pitch_nan_range() {
+    let mut pitch = MpePitch::new(60);
+    pitch.note_bend_range = f32::NAN;
+    pitch.frequency();
+}
+
+#[test]
+#[should_panic(expected = "pitch bend range inf is not finite")]
+fn test_mpe_pitch_infinite_range() {
+    let mut pitch = MpePitch::new(60);
+    pitch.channel_bend_range = f32::INFINITY;
+    pitch.frequency_approx();
+}
+
+#[test]
+fn test_key_bend_to_frequency() {
+    use crate::key_to_frequency_raw;
+
+    for k in 0..=127 {
+        assert_eq!(
+            key_bend_to_frequency_default(k, 8192),
+            key_to_frequency_raw(k)
+        );
+    }
+    for k in 2..=125 {
+        let down = key_bend_to_frequency_default(k, 0);
+        let up = key_bend_to_frequency_default(k, 16383);
+        assert!(f32::abs(down - key_to_frequency_raw(k - 2)) < 1.0e-5 * down);
+        assert!(f32::abs(up - key_to_frequency_raw(k + 2)) < 1.0e-5 * up);
+        let approx = key_bend_to_frequency_approx(k, 16383, 2.0);
+        assert!(f32::abs(up - approx) < 1.0e-5 * up);
+    }
+
+    // Bends past the ends of the key range clamp.
+    assert_eq!(key_bend_to_frequency_default(0, 0), key_to_frequency_raw(0));
+    assert_eq!(
+        key_bend_to_frequency_default(127, 16383),
+        key_to_frequency_raw(127)
+    );
+    assert_eq!(
+        key_bend_to_frequency(120, 16383, 24.0),
+        key_to_frequency_raw(127)
+    );
+}
+
+#[test]
+#[should_panic(expected = "pitch bend 16384 out of range 0..=16383")]
+fn test_key_bend_out_of_range() {
+    key_bend_to_frequency_default(69, 16384);
+}
+
+#[test]
+fn test_mpe_pitch() {
+    use crate::key_to_frequency_raw;
+
+    let mut pitch = MpePitch::new(60);
+    assert_eq!(pitch.frequency(), key_to_frequency_raw(60));
+
+    // 6144 is exactly -12 semitones with a 48 semitone range.
+    pitch.note_bend = 6144;
+    assert_eq!(pitch.frequency(), key_to_frequency_raw(48));
+
+    pitch.note_bend = 16383;
+    pitch.note_bend_range = 12.0;
+    let f = pitch.frequency();
+    assert!(f32::abs(f - 2.0 * key_to_frequency_raw(60)) < 1.0e-5 * f);
+    let g = pitch.frequency_approx();
+    assert!(f32::abs(f - g) < 1.0e-5 * f);
+
+    // Note and channel bends add.
+    pitch.note_bend = 4096;
+    pitch.note_bend_range = 2.0;
+    pitch.channel_bend = 4096;
+    assert_eq!(pitch.frequency(), key_to_frequency_raw(58));
+
+    // Extreme bends clamp.
+    let mut pitch = MpePitch::new(10);
+    pitch.note_bend = 0;
+    pitch.channel_bend = 0;
+    assert_eq!(pitch.frequency(), key_to_frequency_raw(0));
+    let mut pitch = MpePitch::new(120);
+    pitch.note_bend = 16383;
+    pitch.channel_bend = 16383;
+    assert_eq!(pitch.frequency(), key_to_frequency_raw(127));
+    assert_eq!(
+        pitch.frequency_approx().round(),
+        key_to_frequency_raw(127).round()
+    );
+}
+
+#[test]
+fn test_key_bend_to_frequency_checked() {
+    for k in 0..=255 {
+        for bend in [0, 8192, 16383, 16384, u16::MAX] {
+            let checked = key_bend_to_frequency_checked(k, bend, 2.0);
+            if k < 128 && bend < 16384 {
+                assert_eq!(checked, Some(key_bend_to_frequency(k, bend, 2.0)));
+            } else {
+                assert_eq!(checked, None);
+            }
+        }
+    }
+    for range in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+        for bend in [0, 8192, 16383] {
+            assert_eq!(key_bend_to_frequency_checked(69, bend, range), None);
+        }
+    }
+}
@@ -0,0 +1,381 @@
+//! Pitch measured in cents.
+
+#[cfg(all(not(feature = "std"), not(test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::{
+    check_key, fractional_key_to_frequency, frequency_to_key_fractional,
+    key_to_frequency_approx_raw, key_to_frequency_raw, or_panic, semitones_to_ratio_approx,
+};
+
+/// Returns the absolute pitch of the given midi key in
+/// cents above key 0 (C-1, about 8.18 Hz). This is exactly
+/// 100 times the key.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_cents;
+/// assert_eq!(key_to_cents(69), 6900.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_cents(key: u8) -> f32 {
+    or_panic(check_key(key)) as f32 * 100.0
+}
+
+/// Computes the absolute pitch of the given frequency in Hz
+/// in cents above key 0 (C-1, about 8.18 Hz), using the
+/// formula
+///    $$1200 \log_2 \frac{f}{f_0}$$
+/// where $f_0$ is the frequency of key 0. The result is not
+/// clamped, and is negative for frequencies below key 0. A
+/// frequency that is not a positive number gives a NaN or
+/// infinite result.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::frequency_to_cents;
+/// assert_eq!(frequency_to_cents(440.0), 6900.0);
+/// ```
+pub fn frequency_to_cents(freq: f32) -> f32 {
+    100.0 * frequency_to_key_fractional(freq)
+}
+
+/// Computes the frequency in Hz of an absolute pitch in
+/// cents above key 0 (C-1, about 8.18 Hz). This is the
+/// inverse of [frequency_to_cents].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::cents_to_frequency;
+/// assert_eq!(cents_to_frequency(6900.0), 440.0);
+/// ```
+pub fn cents_to_frequency(cents: f32) -> f32 {
+    fractional_key_to_frequency(cents / 100.0)
+}
+
+/// Check that `cents` is at most a semitone either way.
+fn check_semitone_cents(cents: f32) {
+    assert!(
+        (-100.0..=100.0).contains(&cents),
+        "cents {} out of range -100.0..=100.0",
+        cents,
+    );
+}
+
+/// Computes the frequency of the given midi key offset by
+/// the given number of cents, using the formula
+///    $$f_k \cdot 2^{\frac{c}{1200}}$$
+/// where $f_k$ is the frequency of the key and $c$ the
+/// cents offset.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_plus_cents_to_frequency;
+/// assert_eq!(key_plus_cents_to_frequency(69, 0.0), 440.0);
+/// assert_eq!(key_plus_cents_to_frequency(69, 100.0).round(), 466.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127` or `cents`
+/// is not in the range `-100.0..=100.0`.
+pub fn key_plus_cents_to_frequency(key: u8, cents: f32) -> f32 {
+    check_semitone_cents(cents);
+    key_to_frequency_raw(key) * f32::powf(2.0, cents / 1200.0)
+}
+
+/// Computes the approximate frequency of the given midi key
+/// offset by the given number of cents, as
+/// [key_plus_cents_to_frequency] but using
+/// [key_to_frequency_approx_raw] and a Chebyshev series for the
+/// cents factor.
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127` or `cents`
+/// is not in the range `-100.0..=100.0`.
+pub fn key_plus_cents_to_frequency_approx(key: u8, cents: f32) -> f32 {
+    check_semitone_cents(cents);
+    key_to_frequency_approx_raw(key) * semitones_to_ratio_approx(cents / 100.0)
+}
+
+#[test]
+fn test_cents() {
+    use crate::key_to_frequency_raw;
+
+    for k in 0..=127 {
+        let cents = key_to_cents(k);
+        assert_eq!(cents, 100.0 * k as f32);
+        assert_eq!(cents_to_frequency(cents), key_to_frequency_raw(k));
+        let c = frequency_to_cents(key_to_frequency_raw(k));
+        assert!(f32::abs(c - cents) < 0.01, "{} {}", k, c);
+    }
+
+    assert!(f32::abs(cents_to_frequency(0.0) - 8.175_799) < 1.0e-5);
+    assert!(f32::abs(frequency_to_cents(8.175_799)) < 0.01);
+    for cents in [-1200.0, 1.0, 6950.0, 12750.0, 15000.0] {
+        let c = frequency_to_cents(cents_to_frequency(cents));
+        assert!(f32::abs(c - cents) < 0.01, "{} {}", cents, c);
+    }
+}
+
+/// Computes the frequency of the given midi key detuned by
+/// the given number of cents, using the formula
+///    $$440 \cdot 2^{\frac{k - 69}{12} + \frac{c}{1200}}$$
+/// Unlike [key_plus_cents_to_frequency], any detune is
+/// allowed. A detune that moves the pitch outside the range
+/// of midi keys gives the frequency the formula gives; it is
+/// not clamped.
+///
+/// Detuning by a whole number of semitones gives exactly the
+/// frequency of the key that many semitones away, and a
+/// detune of 0 gives exactly [key_to_frequency_raw].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{key_to_frequency_raw, key_to_frequency_detuned};
+/// assert_eq!(key_to_frequency_detuned(69, 0.0), 440.0);
+/// assert_eq!(key_to_frequency_detuned(69, 1200.0), 880.0);
+/// assert_eq!(key_to_frequency_detuned(69, -100.0), key_to_frequency_raw(68));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_frequency_detuned(key: u8, cents: f32) -> f32 {
+    let key = or_panic(check_key(key));
+    fractional_key_to_frequency(key as f32 + cents / 100.0)
+}
+
+/// Computes the approximate frequency of the given midi key
+/// detuned by the given number of cents, as
+/// [key_to_frequency_detuned] but multiplying
+/// [key_to_frequency_approx_raw] by a Chebyshev approximation of
+/// the detune ratio. The detune ratio is accurate to better
+/// than 0.01¢ wherever it is a normal `f32`. A detune by a
+/// whole number of octaves, including 0, is exact, and a
+/// detune too large for an `f32` gives infinity or zero as
+/// [key_to_frequency_detuned] does.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{key_to_frequency_approx_raw, key_to_frequency_detuned_approx};
+/// assert_eq!(key_to_frequency_detuned_approx(60, 0.0), key_to_frequency_approx_raw(60));
+/// assert_eq!(key_to_frequency_detuned_approx(69, -1200.0), 220.0);
+/// assert_eq!(key_to_frequency_detuned_approx(69, 200_000.0), f32::INFINITY);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_frequency_detuned_approx(key: u8, cents: f32) -> f32 {
+    key_to_frequency_approx_raw(key) * semitones_to_ratio_approx(cents / 100.0)
+}
+
+#[test]
+fn test_key_plus_cents_to_frequency() {
+    for k in 0..=127 {
+        assert_eq!(key_plus_cents_to_frequency(k, 0.0), key_to_frequency_raw(k));
+        for cents in [-100.0, -50.0, -1.0, 1.0, 33.3, 100.0] {
+            let f = key_plus_cents_to_frequency(k, cents);
+            let c = frequency_to_cents(f) - key_to_cents(k);
+            assert!(f32::abs(c - cents) < 0.01, "{} {} {}", k, cents, c);
+            let a = key_plus_cents_to_frequency_approx(k, cents);
+            assert!(f32::abs(f - a) < 0.001 * f, "{} {} {}", k, f, a);
+        }
+    }
+    for k in 0..=126 {
+        let f = key_plus_cents_to_frequency(k, 100.0);
+        assert!(f32::abs(f - key_to_frequency_raw(k + 1)) < 1.0e-5 * f);
+    }
+}
+
+#[test]
+#[should_panic(expected = "cents 100.5 out of range -100.0..=100.0")]
+fn test_key_plus_cents_out_of_range() {
+    key_plus_cents_to_frequency(69, 100.5);
+}
+
+/// Computes the acoustic beat frequency in Hz between two
+/// midi keys sounding together: the absolute difference of
+/// their frequencies.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::acoustic_beat_frequency;
+/// assert_eq!(acoustic_beat_frequency(57, 69), 220.0);
+/// assert_eq!(acoustic_beat_frequency(69, 57), 220.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key_a` or `key_b` is not in the range
+/// `0..=127`.
+pub fn acoustic_beat_frequency(key_a: u8, key_b: u8) -> f32 {
+    f32::abs(key_to_frequency_raw(key_a) - key_to_frequency_raw(key_b))
+}
+
+/// Computes the beat frequency in Hz between the given midi
+/// key and a copy of it detuned by the given number of
+/// cents, using the formula
+///    $$f_k \left| 2^{\frac{c}{1200}} - 1 \right|$$
+/// where $f_k$ is the frequency of the key and $c$ the
+/// detuning. This is the rate at which chorus and unison
+/// detune effects beat.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::beat_frequency_with_detuning;
+/// // Two A4 voices 10¢ apart beat about 2.5 times a second.
+/// assert_eq!((beat_frequency_with_detuning(69, 10.0) * 10.0).round(), 25.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn beat_frequency_with_detuning(key: u8, cents: f32) -> f32 {
+    key_to_frequency_raw(key) * f32::abs(f32::powf(2.0, cents / 1200.0) - 1.0)
+}
+
+#[test]
+fn test_beat_frequency() {
+    for k in 0..=127 {
+        assert_eq!(acoustic_beat_frequency(k, k), 0.0);
+        assert_eq!(beat_frequency_with_detuning(k, 0.0), 0.0);
+    }
+    for k in 0..=126 {
+        let a = acoustic_beat_frequency(k, k + 1);
+        let b = beat_frequency_with_detuning(k, 100.0);
+        assert!(f32::abs(a - b) < 1.0e-4 * a, "{} {} {}", k, a, b);
+    }
+    let up = beat_frequency_with_detuning(69, 7.0);
+    let down = beat_frequency_with_detuning(69, -7.0);
+    assert!(up > down && down > 0.0, "{} {}", up, down);
+}
+
+/// Computes the signed interval in cents from frequency
+/// `f1` to frequency `f2`, using the formula
+///    $$1200 \log_2 \frac{f_2}{f_1}$$
+/// The result is positive if `f2` is above `f1`, and exactly
+/// 0.0 if they are equal. If either frequency is not a
+/// finite positive number the result is NaN.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::cents_between;
+/// assert_eq!(cents_between(440.0, 880.0), 1200.0);
+/// assert_eq!(cents_between(880.0, 440.0), -1200.0);
+/// assert!(cents_between(0.0, 440.0).is_nan());
+/// ```
+pub fn cents_between(f1: f32, f2: f32) -> f32 {
+    let valid = |f: f32| f > 0.0 && f.is_finite();
+    if !valid(f1) || !valid(f2) {
+        return f32::NAN;
+    }
+    if f1 == f2 {
+        return 0.0;
+    }
+    1200.0 * f32::log2(f2 / f1)
+}
+
+#[test]
+fn test_cents_between() {
+    for k in 0..=127 {
+        let f = key_to_frequency_raw(k);
+        assert_eq!(cents_between(f, f), 0.0);
+        for j in [0, 1, 12, 69, 127] {
+            let c = cents_between(f, key_to_frequency_raw(j));
+            let expected = 100.0 * (j as f32 - k as f32);
+            assert!(f32::abs(c - expected) < 0.01, "{} {} {}", k, j, c);
+        }
+    }
+    assert_eq!(cents_between(f32::MIN_POSITIVE, f32::MIN_POSITIVE), 0.0);
+    assert_eq!(cents_between(f32::MAX, f32::MAX), 0.0);
+    for bad in [
+        0.0,
+        -0.0,
+        -440.0,
+        f32::NAN,
+        f32::INFINITY,
+        f32::NEG_INFINITY,
+    ] {
+        assert!(cents_between(bad, 440.0).is_nan(), "{}", bad);
+        assert!(cents_between(440.0, bad).is_nan(), "{}", bad);
+        assert!(cents_between(bad, bad).is_nan(), "{}", bad);
+    }
+}
+
+#[test]
+fn test_key_to_frequency_detuned() {
+    for k in 0..=127 {
+        assert_eq!(key_to_frequency_detuned(k, 0.0), key_to_frequency_raw(k));
+        if k > 0 {
+            assert_eq!(
+                key_to_frequency_detuned(k, -100.0),
+                key_to_frequency_raw(k - 1)
+            );
+        }
+        if k < 127 {
+            assert_eq!(
+                key_to_frequency_detuned(k, 100.0),
+                key_to_frequency_raw(k + 1)
+            );
+        }
+        for cents in [-250.0, -200.0, -37.5, 1.0, 49.0, 200.0, 250.0] {
+            let f = key_to_frequency_detuned(k, cents);
+            let c = frequency_to_cents(f) - key_to_cents(k);
+            assert!(f32::abs(c - cents) < 0.01, "{} {} {}", k, cents, c);
+            let a = key_to_frequency_detuned_approx(k, cents);
+            let c = 1200.0 * f32::log2(a / f);
+            assert!(f32::abs(c) < 0.13, "{} {} {}", k, cents, c);
+        }
+    }
+    assert_eq!(
+        key_to_frequency_detuned(127, 1200.0),
+        2.0 * key_to_frequency_raw(127)
+    );
+    assert!(key_to_frequency_detuned(0, -1200.0) < key_to_frequency_raw(0));
+
+    // Zero and whole-octave detunes of the approximation are
+    // exact.
+    for k in 0..=127 {
+        let f = key_to_frequency_approx_raw(k);
+        assert_eq!(key_to_frequency_detuned_approx(k, 0.0), f);
+        assert_eq!(key_plus_cents_to_frequency_approx(k, 0.0), f);
+        assert_eq!(key_to_frequency_detuned_approx(k, 2400.0), 4.0 * f);
+        assert_eq!(key_to_frequency_detuned_approx(k, -1200.0), 0.5 * f);
+    }
+
+    // Huge detunes saturate like the exact version.
+    for cents in [200_000.0, 1_000_000.0] {
+        assert_eq!(key_to_frequency_detuned(69, cents), f32::INFINITY);
+        assert_eq!(key_to_frequency_detuned_approx(69, cents), f32::INFINITY);
+        assert_eq!(key_to_frequency_detuned(69, -cents), 0.0);
+        assert_eq!(key_to_frequency_detuned_approx(69, -cents), 0.0);
+    }
+    // Tiny results stay positive down into the subnormals.
+    for cents in [-150_000.0, -165_000.0, -175_000.0] {
+        let f = key_to_frequency_detuned(69, cents);
+        let a = key_to_frequency_detuned_approx(69, cents);
+        assert!(
+            a > 0.0 && f32::abs(a - f) <= 0.001 * f,
+            "{} {} {}",
+            cents,
+            f,
+            a
+        );
+    }
+}
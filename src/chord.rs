@@ -0,0 +1,203 @@
+//! Chords built on a midi key.
+
+use crate::{check_key, key_to_frequency_approx_raw, key_to_frequency_raw, or_panic};
+
+/// Type of a chord, as used by [chord_frequencies].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChordType {
+    /// Major triad: root, major third, fifth.
+    Major,
+    /// Minor triad: root, minor third, fifth.
+    Minor,
+    /// Diminished triad: root, minor third, diminished fifth.
+    Diminished,
+    /// Augmented triad: root, major third, augmented fifth.
+    Augmented,
+    /// Suspended second: root, major second, fifth.
+    Sus2,
+    /// Suspended fourth: root, perfect fourth, fifth.
+    Sus4,
+    /// Major sixth: major triad plus major sixth.
+    MajorSixth,
+    /// Minor sixth: minor triad plus major sixth.
+    MinorSixth,
+    /// Dominant seventh: major triad plus minor seventh.
+    DominantSeventh,
+    /// Major seventh: major triad plus major seventh.
+    MajorSeventh,
+    /// Minor seventh: minor triad plus minor seventh.
+    MinorSeventh,
+    /// Minor-major seventh: minor triad plus major seventh.
+    MinorMajorSeventh,
+    /// Half-diminished seventh: diminished triad plus minor seventh.
+    HalfDiminishedSeventh,
+    /// Diminished seventh: diminished triad plus diminished seventh.
+    DiminishedSeventh,
+    /// Augmented seventh: augmented triad plus minor seventh.
+    AugmentedSeventh,
+    /// Dominant ninth: dominant seventh plus major ninth.
+    DominantNinth,
+    /// Major ninth: major seventh plus major ninth.
+    MajorNinth,
+    /// Minor ninth: minor seventh plus major ninth.
+    MinorNinth,
+    /// Added ninth: major triad plus major ninth, with no seventh.
+    AddNine,
+    /// Dominant eleventh: dominant ninth plus perfect eleventh.
+    DominantEleventh,
+    /// Dominant thirteenth: dominant eleventh plus major thirteenth.
+    DominantThirteenth,
+}
+
+impl ChordType {
+    /// Offsets in semitones above the root of the notes of
+    /// this chord, in ascending order starting with the root
+    /// itself. There are at most [MAX_CHORD_NOTES] of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use keytones::ChordType;
+    /// assert_eq!(ChordType::DominantSeventh.semitones(), &[0, 4, 7, 10]);
+    /// ```
+    pub const fn semitones(self) -> &'static [u8] {
+        use ChordType::*;
+        match self {
+            Major => &[0, 4, 7],
+            Minor => &[0, 3, 7],
+            Diminished => &[0, 3, 6],
+            Augmented => &[0, 4, 8],
+            Sus2 => &[0, 2, 7],
+            Sus4 => &[0, 5, 7],
+            MajorSixth => &[0, 4, 7, 9],
+            MinorSixth => &[0, 3, 7, 9],
+            DominantSeventh => &[0, 4, 7, 10],
+            MajorSeventh => &[0, 4, 7, 11],
+            MinorSeventh => &[0, 3, 7, 10],
+            MinorMajorSeventh => &[0, 3, 7, 11],
+            HalfDiminishedSeventh => &[0, 3, 6, 10],
+            DiminishedSeventh => &[0, 3, 6, 9],
+            AugmentedSeventh => &[0, 4, 8, 10],
+            DominantNinth => &[0, 4, 7, 10, 14],
+            MajorNinth => &[0, 4, 7, 11, 14],
+            MinorNinth => &[0, 3, 7, 10, 14],
+            AddNine => &[0, 4, 7, 14],
+            DominantEleventh => &[0, 4, 7, 10, 14, 17],
+            DominantThirteenth => &[0, 4, 7, 10, 14, 17, 21],
+        }
+    }
+}
+
+/// Largest number of notes in a [ChordType].
+pub const MAX_CHORD_NOTES: usize = 7;
+
+/// Frequencies of the notes of a chord, using `convert` to
+/// find the frequency of each key.
+fn chord(
+    root: u8,
+    chord_type: ChordType,
+    convert: fn(u8) -> f32,
+) -> [Option<f32>; MAX_CHORD_NOTES] {
+    let root = or_panic(check_key(root));
+    let mut freqs = [None; MAX_CHORD_NOTES];
+    for (f, &s) in freqs.iter_mut().zip(chord_type.semitones()) {
+        *f = check_key(root + s).ok().map(convert);
+    }
+    freqs
+}
+
+/// Returns the frequencies of the notes of the chord of the
+/// given type with the given midi key as its root, in the
+/// order of [ChordType::semitones]. Entries past the last
+/// note of the chord, and entries for notes above key 127,
+/// are `None`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{chord_frequencies, ChordType};
+/// let chord = chord_frequencies(57, ChordType::Major);
+/// assert_eq!(chord[0], Some(220.0));
+/// assert_eq!(chord[1].map(f32::round), Some(277.0));
+/// assert_eq!(chord[2].map(f32::round), Some(330.0));
+/// assert_eq!(chord[3], None);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `root` is not in the range `0..=127`.
+pub fn chord_frequencies(root: u8, chord_type: ChordType) -> [Option<f32>; MAX_CHORD_NOTES] {
+    chord(root, chord_type, key_to_frequency_raw)
+}
+
+/// Returns the approximate frequencies of the notes of a
+/// chord as [chord_frequencies] does, but using
+/// [key_to_frequency_approx_raw].
+///
+/// # Panics
+///
+/// Panics if `root` is not in the range `0..=127`.
+pub fn chord_frequencies_approx(root: u8, chord_type: ChordType) -> [Option<f32>; MAX_CHORD_NOTES] {
+    chord(root, chord_type, key_to_frequency_approx_raw)
+}
+
+#[test]
+fn test_chord_frequencies() {
+    use ChordType::*;
+    let all = [
+        Major,
+        Minor,
+        Diminished,
+        Augmented,
+        Sus2,
+        Sus4,
+        MajorSixth,
+        MinorSixth,
+        DominantSeventh,
+        MajorSeventh,
+        MinorSeventh,
+        MinorMajorSeventh,
+        HalfDiminishedSeventh,
+        DiminishedSeventh,
+        AugmentedSeventh,
+        DominantNinth,
+        MajorNinth,
+        MinorNinth,
+        AddNine,
+        DominantEleventh,
+        DominantThirteenth,
+    ];
+    assert_eq!(
+        all.iter().map(|c| c.semitones().len()).max(),
+        Some(MAX_CHORD_NOTES)
+    );
+    for chord_type in all {
+        let semitones = chord_type.semitones();
+        assert!(semitones.len() <= MAX_CHORD_NOTES);
+        assert_eq!(semitones[0], 0);
+        assert!(semitones.windows(2).all(|w| w[0] < w[1]));
+
+        for root in 0..=127 {
+            let exact = chord_frequencies(root, chord_type);
+            let approx = chord_frequencies_approx(root, chord_type);
+            for (i, (e, a)) in exact.iter().zip(&approx).enumerate() {
+                let key = semitones.get(i).map(|&s| root + s).filter(|&k| k <= 127);
+                assert_eq!(*e, key.map(key_to_frequency_raw));
+                assert_eq!(*a, key.map(key_to_frequency_approx_raw));
+            }
+        }
+    }
+
+    let chord = chord_frequencies(120, DominantSeventh);
+    assert_eq!(
+        chord[..3],
+        [120, 124, 127].map(|k| Some(key_to_frequency_raw(k)))
+    );
+    assert_eq!(chord[3], None);
+}
+
+#[test]
+#[should_panic(expected = "key 128 out of range 0..=127")]
+fn test_chord_frequencies_out_of_range() {
+    chord_frequencies(128, ChordType::Major);
+}
@@ -0,0 +1,109 @@
+// GENERATED by build.rs: regenerate with the regen-consts feature.
+
+/// Number of coefficients in [CHEBYSHEV_TOP_OCTAVE] and
+/// [CHEBYSHEV_BOTTOM_OCTAVE].
+pub const CHEBYSHEV_ORDER: usize = 4;
+
+/// Chebyshev series coefficients for top octave frequencies,
+/// as used by [key_to_frequency_approx](crate::key_to_frequency_approx).
+///
+/// The series approximates the frequency in Hz of key
+/// $116 + x$, $440 \cdot 2^{(x + 47) / 12}$, over the domain
+/// $0 \le x \le 11$. Frequencies of lower keys are found
+/// by halving once per octave. The maximum error is less
+/// than 0.12¢.
+///
+/// The coefficients are for the Chebyshev polynomials
+/// $T_0 \ldots T_3$ of $t = 2x / 11 - 1$. The $T_0$
+/// coefficient is stored already halved, so that
+/// $f(x) = c_0 + \sum_{i \ge 1} c_i T_i(t)$.
+pub const CHEBYSHEV_TOP_OCTAVE: [f32; CHEBYSHEV_ORDER] = [9361.57, 2937.1982, 232.30713, 12.258301];
+
+/// Chebyshev series coefficients for bottom octave periods,
+/// as used by [key_to_period_approx](crate::key_to_period_approx).
+///
+/// The series approximates the unit period in seconds of
+/// key $x$, $1 / (440 \cdot 2^{(x - 69) / 12})$, over the
+/// domain $0 \le x \le 11$. Periods of higher keys are
+/// found by halving once per octave. The maximum error is
+/// less than 0.12¢.
+///
+/// The coefficients are laid out as for
+/// [CHEBYSHEV_TOP_OCTAVE].
+pub const CHEBYSHEV_BOTTOM_OCTAVE: [f32; CHEBYSHEV_ORDER] = [0.091282494, -0.02863995, 0.0022651702, -0.00011954643];
+
+/// Chebyshev series constants for frequency ratios of
+/// fractional semitones in a full octave.
+pub const CHEBYSHEV_OCTAVE: [f32; 5] = [1.457, 0.4975248, 0.04289323, 0.0024715066, 0.000106588006];
+
+/// Chebyshev series constants for fractional keys of
+/// frequencies in a single octave from 1 to 2 Hz.
+pub const CHEBYSHEV_LOG_OCTAVE: [f32; 6] = [-29.859037, 5.9406567, -0.509635, 0.05829048, -0.0075120926, 0.0010080338];
+
+/// Double-precision Chebyshev series constants for top
+/// octave frequencies.
+pub const CHEBYSHEV_TOP_OCTAVE_F64: [f64; 10] = [9361.570681843721, 2937.199365157031, 232.3066369343212, 12.274567448764628, 0.48682851752937495, 0.01545318540001972, 0.0004088678452944805, 9.27398173189431e-6, 1.840784875639656e-7, 3.246981350457645e-9];
+
+/// Double-precision Chebyshev series constants for bottom
+/// octave periods.
+pub const CHEBYSHEV_BOTTOM_OCTAVE_F64: [f64; 10] = [0.09128250096018939, -0.028639948677651836, 0.002265168050286974, -0.00011968645572489611, 4.746951780782577e-6, -1.5068042098428935e-7, 3.986775336944914e-9, -9.04284303687719e-11, 1.794868073612399e-12, -3.171161250259402e-14];
+
+/// Order-3 Chebyshev series constants for top octave
+/// frequencies.
+pub const CHEBYSHEV_TOP_OCTAVE_3: [f32; 3] = [9361.57, 2937.182, 231.8208];
+
+/// Order-6 Chebyshev series constants for top octave
+/// frequencies.
+pub const CHEBYSHEV_TOP_OCTAVE_6: [f32; 6] = [9361.571, 2937.1987, 232.3081, 12.273682, 0.4901123, 0.01751709];
+
+/// Higher-order Chebyshev series constants for top octave
+/// frequencies.
+pub const CHEBYSHEV_TOP_OCTAVE_HI: [f32; 8] = [9361.57, 2937.1995, 232.30664, 12.274568, 0.4868285, 0.015453185, 0.0004088678, 9.270733e-6];
+
+/// Higher-order Chebyshev series constants for bottom
+/// octave periods.
+pub const CHEBYSHEV_BOTTOM_OCTAVE_HI: [f32; 8] = [0.0912825, -0.028639948, 0.0022651681, -0.000119686454, 4.7469516e-6, -1.5068042e-7, 3.986775e-9, -9.039679e-11];
+
+/// Frequency in Hz of each midi key, indexed by key. The
+/// entries are identical to the results of
+/// [key_to_frequency](crate::key_to_frequency).
+///
+/// ```
+/// # use keytones::FREQUENCY_TABLE;
+/// assert_eq!(FREQUENCY_TABLE[69], 440.0);
+/// ```
+pub const FREQUENCY_TABLE: [f32; 128] = [8.175798, 8.661958, 9.177023, 9.722718, 10.300862, 10.913382, 11.5623255, 12.249859, 12.97827, 13.75, 14.56762, 15.433851, 16.351597, 17.323915, 18.354046, 19.445436, 20.601725, 21.826763, 23.124651, 24.499718, 25.95654, 27.5, 29.135233, 30.86771, 32.703194, 34.647827, 36.7081, 38.890873, 41.20344, 43.65353, 46.249302, 48.999424, 51.91309, 55.0, 58.270466, 61.73542, 65.40639, 69.295654, 73.4162, 77.781746, 82.40688, 87.30706, 92.498604, 97.99885, 103.82618, 110.0, 116.54095, 123.470825, 130.81277, 138.59132, 146.83238, 155.56349, 164.81378, 174.61412, 184.99721, 195.99773, 207.65234, 220.0, 233.08186, 246.94165, 261.62555, 277.18265, 293.66476, 311.12698, 329.62756, 349.22824, 369.99442, 391.99542, 415.3047, 440.0, 466.1638, 493.8833, 523.2511, 554.3653, 587.3295, 622.25397, 659.2551, 698.4565, 739.98883, 783.99084, 830.6094, 880.0, 932.3276, 987.7666, 1046.5022, 1108.7306, 1174.659, 1244.5079, 1318.5103, 1396.913, 1479.9777, 1567.9818, 1661.2188, 1760.0, 1864.6549, 1975.5334, 2093.0044, 2217.461, 2349.3184, 2489.0159, 2637.0203, 2793.826, 2959.9553, 3135.9631, 3322.4377, 3520.0, 3729.3098, 3951.067, 4186.009, 4434.922, 4698.6367, 4978.0317, 5274.0405, 5587.652, 5919.9106, 6271.9263, 6644.8755, 7040.0, 7458.6216, 7902.132, 8372.018, 8869.845, 9397.271, 9956.063, 10548.083, 11175.303, 11839.821, 12543.855];
+
+/// Unit period in seconds of each midi key, indexed by key.
+/// The entries are identical to the results of
+/// [key_to_period](crate::key_to_period).
+///
+/// ```
+/// # use keytones::PERIOD_TABLE;
+/// assert_eq!(PERIOD_TABLE[69], 1.0 / 440.0);
+/// ```
+pub const PERIOD_TABLE: [f32; 128] = [0.12231221, 0.11544734, 0.1089678, 0.1028519, 0.097079255, 0.09163063, 0.08648779, 0.08163359, 0.07705188, 0.07272727, 0.068645395, 0.06479264, 0.061156105, 0.05772367, 0.0544839, 0.05142595, 0.048539627, 0.045815315, 0.043243896, 0.040816795, 0.03852594, 0.036363635, 0.034322705, 0.032396313, 0.030578053, 0.02886184, 0.027241945, 0.025712974, 0.024269817, 0.022907656, 0.021621948, 0.020408403, 0.019262964, 0.018181818, 0.017161353, 0.016198156, 0.015289026, 0.01443092, 0.013620973, 0.012856487, 0.012134909, 0.011453828, 0.010810974, 0.010204202, 0.009631482, 0.009090909, 0.008580675, 0.008099079, 0.007644513, 0.007215459, 0.006810487, 0.0064282436, 0.006067454, 0.005726914, 0.005405487, 0.0051021, 0.0048157414, 0.0045454544, 0.004290338, 0.0040495396, 0.0038222566, 0.0036077294, 0.0034052434, 0.0032141218, 0.003033727, 0.002863457, 0.0027027435, 0.0025510502, 0.0024078707, 0.0022727272, 0.0021451688, 0.0020247698, 0.0019111283, 0.0018038647, 0.0017026217, 0.0016070609, 0.0015168635, 0.0014317285, 0.0013513718, 0.0012755251, 0.0012039354, 0.0011363636, 0.0010725844, 0.0010123849, 0.00095556414, 0.00090193236, 0.00085131085, 0.00080353045, 0.00075843174, 0.00071586424, 0.0006756859, 0.0006377625, 0.0006019677, 0.0005681818, 0.00053629227, 0.0005061924, 0.00047778207, 0.00045096624, 0.0004256554, 0.00040176522, 0.0003792159, 0.00035793212, 0.00033784294, 0.0003188813, 0.0003009838, 0.0002840909, 0.00026814613, 0.0002530962, 0.00023889104, 0.00022548312, 0.0002128277, 0.00020088261, 0.00018960795, 0.00017896606, 0.00016892147, 0.00015944065, 0.0001504919, 0.00014204545, 0.00013407304, 0.00012654813, 0.00011944552, 0.000112741545, 0.00010641387, 0.000100441306, 9.480396e-5, 8.948304e-5, 8.4460735e-5, 7.97203e-5];
+
+/// Frequency of each midi key rounded to the nearest whole
+/// Hz, indexed by key. The entries are identical to the
+/// rounded results of [key_to_frequency](crate::key_to_frequency).
+///
+/// ```
+/// # use keytones::FREQUENCY_HZ_TABLE;
+/// assert_eq!(FREQUENCY_HZ_TABLE[60], 262);
+/// ```
+pub static FREQUENCY_HZ_TABLE: [u16; 128] = [8, 9, 9, 10, 10, 11, 12, 12, 13, 14, 15, 15, 16, 17, 18, 19, 21, 22, 23, 24, 26, 28, 29, 31, 33, 35, 37, 39, 41, 44, 46, 49, 52, 55, 58, 62, 65, 69, 73, 78, 82, 87, 92, 98, 104, 110, 117, 123, 131, 139, 147, 156, 165, 175, 185, 196, 208, 220, 233, 247, 262, 277, 294, 311, 330, 349, 370, 392, 415, 440, 466, 494, 523, 554, 587, 622, 659, 698, 740, 784, 831, 880, 932, 988, 1047, 1109, 1175, 1245, 1319, 1397, 1480, 1568, 1661, 1760, 1865, 1976, 2093, 2217, 2349, 2489, 2637, 2794, 2960, 3136, 3322, 3520, 3729, 3951, 4186, 4435, 4699, 4978, 5274, 5588, 5920, 6272, 6645, 7040, 7459, 7902, 8372, 8870, 9397, 9956, 10548, 11175, 11840, 12544];
+
+/// Q16.16 fixed-point frequencies of the top octave keys.
+#[cfg(feature = "fixed-point")]
+pub const FREQUENCY_Q16_TOP_OCTAVE: [u32; 12] = [435478539, 461373440, 488808132, 517874176, 548668578, 581294109, 615859655, 652480576, 691279090, 732384684, 775934544, 822074013];
+
+/// Q8.24 fixed-point periods of the bottom octave keys.
+#[cfg(feature = "fixed-point")]
+pub const PERIOD_Q24_BOTTOM_OCTAVE: [u32; 12] = [2052058, 1936885, 1828176, 1725568, 1628720, 1537307, 1451024, 1369585, 1292716, 1220161, 1151679, 1087040];
+
+/// Note name of each midi key with sharp spelling.
+pub const NOTE_NAMES_SHARP: [&str; 128] = ["C-1", "C#-1", "D-1", "D#-1", "E-1", "F-1", "F#-1", "G-1", "G#-1", "A-1", "A#-1", "B-1", "C0", "C#0", "D0", "D#0", "E0", "F0", "F#0", "G0", "G#0", "A0", "A#0", "B0", "C1", "C#1", "D1", "D#1", "E1", "F1", "F#1", "G1", "G#1", "A1", "A#1", "B1", "C2", "C#2", "D2", "D#2", "E2", "F2", "F#2", "G2", "G#2", "A2", "A#2", "B2", "C3", "C#3", "D3", "D#3", "E3", "F3", "F#3", "G3", "G#3", "A3", "A#3", "B3", "C4", "C#4", "D4", "D#4", "E4", "F4", "F#4", "G4", "G#4", "A4", "A#4", "B4", "C5", "C#5", "D5", "D#5", "E5", "F5", "F#5", "G5", "G#5", "A5", "A#5", "B5", "C6", "C#6", "D6", "D#6", "E6", "F6", "F#6", "G6", "G#6", "A6", "A#6", "B6", "C7", "C#7", "D7", "D#7", "E7", "F7", "F#7", "G7", "G#7", "A7", "A#7", "B7", "C8", "C#8", "D8", "D#8", "E8", "F8", "F#8", "G8", "G#8", "A8", "A#8", "B8", "C9", "C#9", "D9", "D#9", "E9", "F9", "F#9", "G9"];
+
+/// Note name of each midi key with flat spelling.
+pub const NOTE_NAMES_FLAT: [&str; 128] = ["C-1", "Db-1", "D-1", "Eb-1", "E-1", "F-1", "Gb-1", "G-1", "Ab-1", "A-1", "Bb-1", "B-1", "C0", "Db0", "D0", "Eb0", "E0", "F0", "Gb0", "G0", "Ab0", "A0", "Bb0", "B0", "C1", "Db1", "D1", "Eb1", "E1", "F1", "Gb1", "G1", "Ab1", "A1", "Bb1", "B1", "C2", "Db2", "D2", "Eb2", "E2", "F2", "Gb2", "G2", "Ab2", "A2", "Bb2", "B2", "C3", "Db3", "D3", "Eb3", "E3", "F3", "Gb3", "G3", "Ab3", "A3", "Bb3", "B3", "C4", "Db4", "D4", "Eb4", "E4", "F4", "Gb4", "G4", "Ab4", "A4", "Bb4", "B4", "C5", "Db5", "D5", "Eb5", "E5", "F5", "Gb5", "G5", "Ab5", "A5", "Bb5", "B5", "C6", "Db6", "D6", "Eb6", "E6", "F6", "Gb6", "G6", "Ab6", "A6", "Bb6", "B6", "C7", "Db7", "D7", "Eb7", "E7", "F7", "Gb7", "G7", "Ab7", "A7", "Bb7", "B7", "C8", "Db8", "D8", "Eb8", "E8", "F8", "Gb8", "G8", "Ab8", "A8", "Bb8", "B8", "C9", "Db9", "D9", "Eb9", "E9", "F9", "Gb9", "G9"];
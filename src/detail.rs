@@ -0,0 +1,120 @@
+//! Building blocks of the approximate conversions, for
+//! users who want to evaluate their own series.
+//!
+//! The approximations split a key into a semitone `m` within
+//! an octave and a number `o` of octaves, evaluate a
+//! Chebyshev series in `m` over a single reference octave,
+//! and scale the result by $2^{-o}$. The reference octave
+//! is chosen so that the scaling only ever divides by a
+//! power of two, which is exact.
+//!
+//! Frequencies grow with the key, so their reference octave
+//! is the *top* one: keys 116 (G♯8) through 127 (G9), with
+//! [key_to_params_top] counting octaves down from it.
+//! Periods shrink with the key, so their reference octave
+//! is the *bottom* one: keys 0 (C-1) through 11 (B-1), with
+//! [key_to_params_bottom] counting octaves up from it.
+//!
+//! # Examples
+//!
+//! ```
+//! # use keytones::{detail::key_to_params_top, key_to_frequency_raw};
+//! // A4 is 4 octaves below A8, semitone 1 of the top octave.
+//! let (m, o) = key_to_params_top(69);
+//! assert_eq!((m, o), (1, 4));
+//! let a8 = key_to_frequency_raw(116 + m);
+//! assert_eq!(a8 / 2.0f32.powi(o as i32), 440.0);
+//! ```
+
+use crate::{KeyError, check_key, or_panic};
+
+/// Splits the given midi key into a semitone `m` in
+/// `0..=11` and a number of octaves `o` in `0..=10`, such
+/// that the key is `116 + m - 12 * o`. The frequency of the
+/// key is the frequency of top-octave key `116 + m` divided
+/// by $2^o$.
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_params_top(key: u8) -> (u8, u8) {
+    or_panic(try_key_to_params_top(key))
+}
+
+/// Like [key_to_params_top], but returns an error rather
+/// than panicking if `key` is not in the range `0..=127`.
+pub fn try_key_to_params_top(key: u8) -> Result<(u8, u8), KeyError> {
+    let key = check_key(key)?;
+    let m = (key + 120 - 116) % 12;
+    let o = 10 - (key + 12 - m) / 12;
+    Ok((m, o))
+}
+
+#[test]
+fn test_key_to_params_top() {
+    let tests: &[(u8, (u8, u8))] = &[
+        (116, (0, 0)),
+        (115, (11, 1)),
+        (69, (1, 4)),
+        (68, (0, 4)),
+        (67, (11, 5)),
+    ];
+    for &(key, vals) in tests {
+        assert_eq!(try_key_to_params_top(key), Ok(vals));
+    }
+    assert_eq!(
+        try_key_to_params_top(128),
+        Err(KeyError::OutOfRange { key: 128 }),
+    );
+    for key in 0..=127 {
+        let (m, o) = key_to_params_top(key);
+        assert!(m < 12 && o <= 10);
+        assert_eq!(116 + m as i32 - 12 * o as i32, key as i32);
+    }
+}
+
+/// Splits the given midi key into a semitone `m` in
+/// `0..=11` and a number of octaves `o` in `0..=10`, such
+/// that the key is `m + 12 * o`. The period of the key is
+/// the period of bottom-octave key `m` divided by $2^o$.
+/// This is the usual pitch class and octave decomposition,
+/// with octaves counted from 0 rather than -1.
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_params_bottom(key: u8) -> (u8, u8) {
+    or_panic(try_key_to_params_bottom(key))
+}
+
+/// Like [key_to_params_bottom], but returns an error rather
+/// than panicking if `key` is not in the range `0..=127`.
+pub fn try_key_to_params_bottom(key: u8) -> Result<(u8, u8), KeyError> {
+    let key = check_key(key)?;
+    let m = key % 12;
+    let o = key / 12;
+    Ok((m, o))
+}
+
+#[test]
+fn test_key_to_params_bottom() {
+    let tests: &[(u8, (u8, u8))] = &[
+        (0, (0, 0)),
+        (1, (1, 0)),
+        (69, (9, 5)),
+        (68, (8, 5)),
+        (67, (7, 5)),
+    ];
+    for &(key, vals) in tests {
+        assert_eq!(try_key_to_params_bottom(key), Ok(vals));
+    }
+    assert_eq!(
+        try_key_to_params_bottom(128),
+        Err(KeyError::OutOfRange { key: 128 }),
+    );
+    for key in 0..=127 {
+        let (m, o) = key_to_params_bottom(key);
+        assert!(m < 12 && o <= 10);
+        assert_eq!(m + 12 * o, key);
+    }
+}
@@ -0,0 +1,180 @@
+//! Integer-only fixed-point conversions, for targets without
+//! floating-point hardware.
+
+use crate::{KeyError, consts, or_panic, try_key_to_params_bottom, try_key_to_params_top};
+
+/// Computes the frequency for a given midi key value in
+/// Q16.16 fixed-point Hz: that is, the frequency multiplied
+/// by 65536. No floating-point arithmetic is used: the top
+/// octave frequencies are stored in a 12-entry table, and
+/// lower octaves are obtained by rounding right shifts.
+///
+/// The sixteen fractional bits keep even the lowest key
+/// (about 8.18 Hz) within 0.01¢ of the exact frequency.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_frequency_q16;
+/// assert_eq!(key_to_frequency_q16(69), 440 << 16);
+/// assert_eq!(key_to_frequency_q16(57), 220 << 16);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_frequency_q16(key: u8) -> u32 {
+    or_panic(try_key_to_frequency_q16(key))
+}
+
+/// Like [key_to_frequency_q16], but returns an error rather
+/// than panicking if `key` is not in the range `0..=127`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{try_key_to_frequency_q16, KeyError};
+/// assert_eq!(try_key_to_frequency_q16(69), Ok(440 << 16));
+/// assert_eq!(try_key_to_frequency_q16(128), Err(KeyError::OutOfRange { key: 128 }));
+/// ```
+pub fn try_key_to_frequency_q16(key: u8) -> Result<u32, KeyError> {
+    let (m, o) = try_key_to_params_top(key)?;
+    let f = consts::FREQUENCY_Q16_TOP_OCTAVE[m as usize];
+    Ok(round_shr(f, o))
+}
+
+/// Computes the unit period for a given midi key value in
+/// Q8.24 fixed-point seconds: that is, the period multiplied
+/// by 2<sup>24</sup>. No floating-point arithmetic or
+/// division is used: the bottom octave periods are stored in
+/// a 12-entry table, and higher octaves are obtained by
+/// rounding right shifts.
+///
+/// The resolution is 2<sup>-24</sup> s, about 60 ns. This
+/// limits accuracy at the highest keys: the period of key
+/// 127 is only about 80 µs, or 1337 units, so rounding
+/// error there is up to about 0.65¢. Below key 116 the error
+/// is less than 0.3¢.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period_q24;
+/// // 1/440 s
+/// assert_eq!(key_to_period_q24(69), 38130);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_period_q24(key: u8) -> u32 {
+    or_panic(try_key_to_period_q24(key))
+}
+
+/// Like [key_to_period_q24], but returns an error rather
+/// than panicking if `key` is not in the range `0..=127`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{try_key_to_period_q24, KeyError};
+/// assert_eq!(try_key_to_period_q24(69), Ok(38130));
+/// assert_eq!(try_key_to_period_q24(128), Err(KeyError::OutOfRange { key: 128 }));
+/// ```
+pub fn try_key_to_period_q24(key: u8) -> Result<u32, KeyError> {
+    let (m, o) = try_key_to_params_bottom(key)?;
+    let p = consts::PERIOD_Q24_BOTTOM_OCTAVE[m as usize];
+    Ok(round_shr(p, o))
+}
+
+/// Computes the unit period for a given midi key value in
+/// Q16.16 fixed-point seconds, in the same format as
+/// [key_to_frequency_q16]. No floating-point arithmetic or
+/// division is used: this is the [key_to_period_q24] table
+/// value shifted right by eight more bits.
+///
+/// The resolution is 2<sup>-16</sup> s, about 15 µs, which is
+/// coarse for short periods: the error is within 1¢ only
+/// up to key 41, and the period of key 127 is just 5 units,
+/// about 76¢ off. Prefer [key_to_period_q24] where its
+/// format will do.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period_q16;
+/// assert_eq!(key_to_period_q16(21), 2383); // 1/27.5 s
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_period_q16(key: u8) -> u32 {
+    or_panic(try_key_to_period_q16(key))
+}
+
+/// Like [key_to_period_q16], but returns an error rather
+/// than panicking if `key` is not in the range `0..=127`.
+pub fn try_key_to_period_q16(key: u8) -> Result<u32, KeyError> {
+    let (m, o) = try_key_to_params_bottom(key)?;
+    let p = consts::PERIOD_Q24_BOTTOM_OCTAVE[m as usize];
+    Ok(round_shr(p, o + 8))
+}
+
+/// Shift `x` right by `n` bits, rounding half up.
+fn round_shr(x: u32, n: u8) -> u32 {
+    if n == 0 {
+        return x;
+    }
+    (x + (1 << (n - 1))) >> n
+}
+
+#[test]
+fn test_key_to_frequency_q16() {
+    for key in 0..=127 {
+        let f = key_to_frequency_q16(key) as f64 / 65536.0;
+        let exact = crate::key_to_frequency_f64(key);
+        let cents = 1200.0 * f64::log2(f / exact);
+        assert!(cents.abs() < 0.01, "{} {} {}", key, f, exact);
+    }
+    assert_eq!(
+        try_key_to_frequency_q16(128),
+        Err(KeyError::OutOfRange { key: 128 }),
+    );
+}
+
+#[test]
+fn test_key_to_period_q24() {
+    let mut max_cents: f64 = 0.0;
+    for key in 0..=127 {
+        let p = key_to_period_q24(key) as f64 / 16777216.0;
+        let exact = crate::key_to_period_raw(key) as f64;
+        let cents = 1200.0 * f64::log2(exact / p);
+        if key < 116 {
+            assert!(cents.abs() < 0.3, "{} {} {}", key, p, exact);
+        }
+        max_cents = max_cents.max(cents.abs());
+    }
+    assert!(max_cents < 0.65, "{}", max_cents);
+    assert_eq!(
+        try_key_to_period_q24(128),
+        Err(KeyError::OutOfRange { key: 128 }),
+    );
+}
+
+#[test]
+fn test_key_to_period_q16() {
+    for key in 0..=127 {
+        let p = key_to_period_q16(key) as f64;
+        let exact = crate::key_to_period_f64(key) * 65536.0;
+        assert!((p - exact).abs() <= 0.5, "{} {} {}", key, p, exact);
+        if key <= 41 {
+            let cents = 1200.0 * f64::log2(exact / p);
+            assert!(cents.abs() < 1.0, "{} {}", key, cents);
+        }
+    }
+    assert_eq!(
+        try_key_to_period_q16(128),
+        Err(KeyError::OutOfRange { key: 128 }),
+    );
+}
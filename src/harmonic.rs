@@ -0,0 +1,98 @@
+//! The harmonic series above a midi key.
+
+#[cfg(feature = "std")]
+use std::{vec, vec::Vec};
+
+use crate::{frequency_to_key_saturating, key_to_frequency_raw};
+
+/// Fills `out` with the frequencies of the harmonic series
+/// of the given midi key, starting with the fundamental:
+/// `out[n]` is `n + 1` times the frequency of the key.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::harmonic_series_frequencies_into;
+/// let mut out = [0.0; 4];
+/// harmonic_series_frequencies_into(45, &mut out);
+/// assert_eq!(out, [110.0, 220.0, 330.0, 440.0]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn harmonic_series_frequencies_into(key: u8, out: &mut [f32]) {
+    let f = key_to_frequency_raw(key);
+    for (n, h) in out.iter_mut().enumerate() {
+        *h = f * (n + 1) as f32;
+    }
+}
+
+/// Returns the frequencies of the first `count` harmonics of
+/// the given midi key, as
+/// [harmonic_series_frequencies_into] does.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::harmonic_series_frequencies;
+/// assert_eq!(harmonic_series_frequencies(45, 3), [110.0, 220.0, 330.0]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+#[cfg(feature = "std")]
+pub fn harmonic_series_frequencies(key: u8, count: usize) -> Vec<f32> {
+    let mut out = vec![0.0; count];
+    harmonic_series_frequencies_into(key, &mut out);
+    out
+}
+
+/// Finds the midi key nearest in pitch to the given
+/// harmonic of the given midi key. Harmonic 1 is the
+/// fundamental. Harmonics above key 127 give 127.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::harmonic_to_nearest_key;
+/// assert_eq!(harmonic_to_nearest_key(45, 1), 45);
+/// assert_eq!(harmonic_to_nearest_key(45, 3), 64);
+/// // The seventh harmonic is about 31¢ flat of key 79.
+/// assert_eq!(harmonic_to_nearest_key(45, 7), 79);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127` or
+/// `harmonic` is 0.
+pub fn harmonic_to_nearest_key(key: u8, harmonic: u32) -> u8 {
+    assert!(harmonic > 0, "harmonic must be positive");
+    frequency_to_key_saturating(key_to_frequency_raw(key) * harmonic as f32)
+}
+
+#[test]
+fn test_harmonic_series() {
+    let mut out = [0.0; 8];
+    harmonic_series_frequencies_into(45, &mut out);
+    let expected = [110.0, 220.0, 330.0, 440.0, 550.0, 660.0, 770.0, 880.0];
+    for (f, e) in out.iter().zip(expected) {
+        assert!(f32::abs(f - e) < 1.0e-4, "{} {}", f, e);
+    }
+
+    // Octave harmonics land exactly on keys.
+    for (h, offset) in [(1, 0), (2, 12), (4, 24), (8, 36)] {
+        assert_eq!(harmonic_to_nearest_key(45, h), 45 + offset);
+    }
+    // Fifth and major third harmonics.
+    assert_eq!(harmonic_to_nearest_key(45, 3), 64);
+    assert_eq!(harmonic_to_nearest_key(45, 5), 73);
+    assert_eq!(harmonic_to_nearest_key(120, 16), 127);
+}
+
+#[test]
+#[should_panic(expected = "harmonic must be positive")]
+fn test_harmonic_zero() {
+    harmonic_to_nearest_key(45, 0);
+}
@@ -0,0 +1,529 @@
+//! Musical intervals between midi keys.
+
+#[cfg(all(not(feature = "std"), not(test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::{check_key, or_panic, semitones_to_ratio_approx};
+
+/// Name of a simple musical interval, as returned by
+/// [classify_interval].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntervalName {
+    /// Unison: 0 semitones.
+    Unison,
+    /// Minor second: 1 semitone.
+    MinorSecond,
+    /// Major second: 2 semitones.
+    MajorSecond,
+    /// Minor third: 3 semitones.
+    MinorThird,
+    /// Major third: 4 semitones.
+    MajorThird,
+    /// Perfect fourth: 5 semitones.
+    PerfectFourth,
+    /// Tritone: 6 semitones.
+    Tritone,
+    /// Perfect fifth: 7 semitones.
+    PerfectFifth,
+    /// Minor sixth: 8 semitones.
+    MinorSixth,
+    /// Major sixth: 9 semitones.
+    MajorSixth,
+    /// Minor seventh: 10 semitones.
+    MinorSeventh,
+    /// Major seventh: 11 semitones.
+    MajorSeventh,
+    /// Octave: 12 semitones.
+    Octave,
+    /// Any interval outside `0..=12` semitones, which is
+    /// given.
+    Other(i8),
+}
+
+/// Number of semitones from midi key `low` up to midi key
+/// `high`. This is negative if `high` is below `low`; since
+/// both keys are in range, the result is in `-127..=127`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::interval_semitones;
+/// assert_eq!(interval_semitones(60, 67), 7);
+/// assert_eq!(interval_semitones(67, 60), -7);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `low` or `high` is not in the range `0..=127`.
+pub fn interval_semitones(low: u8, high: u8) -> i8 {
+    let low = or_panic(check_key(low));
+    let high = or_panic(check_key(high));
+    high as i8 - low as i8
+}
+
+/// Ratio of the frequency of midi key `high` to that of
+/// midi key `low`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::frequency_ratio;
+/// assert_eq!(frequency_ratio(57, 69), 2.0);
+/// assert!((frequency_ratio(60, 67) - 1.498).abs() < 0.001);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `low` or `high` is not in the range `0..=127`.
+pub fn frequency_ratio(low: u8, high: u8) -> f32 {
+    ratio_between_keys(low, high)
+}
+
+/// Equal-tempered frequency ratio of midi key `k2` to midi
+/// key `k1`: $2^{(k_2 - k_1)/12}$. The ratio is computed
+/// from the interval rather than by dividing two
+/// frequencies, so that it is exactly a power of two when
+/// the keys are whole octaves apart.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::ratio_between_keys;
+/// assert_eq!(ratio_between_keys(60, 96), 8.0);
+/// assert_eq!(ratio_between_keys(96, 60), 0.125);
+/// assert!((ratio_between_keys(60, 67) - 1.498).abs() < 0.001);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `k1` or `k2` is not in the range `0..=127`.
+pub fn ratio_between_keys(k1: u8, k2: u8) -> f32 {
+    let semitones = interval_semitones(k1, k2) as i32;
+    let octaves = semitones.div_euclid(12);
+    // A power of two built directly from its exponent bits.
+    let scale = f32::from_bits(((127 + octaves) as u32) << 23);
+    semitone_ratio(semitones.rem_euclid(12) as i8) * scale
+}
+
+/// Name of the interval spanning the given number of
+/// semitones. Intervals outside of a single ascending
+/// octave are [IntervalName::Other].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{classify_interval, IntervalName};
+/// assert_eq!(classify_interval(7), IntervalName::PerfectFifth);
+/// assert_eq!(classify_interval(-7), IntervalName::Other(-7));
+/// ```
+pub fn classify_interval(semitones: i8) -> IntervalName {
+    use IntervalName::*;
+    match semitones {
+        0 => Unison,
+        1 => MinorSecond,
+        2 => MajorSecond,
+        3 => MinorThird,
+        4 => MajorThird,
+        5 => PerfectFourth,
+        6 => Tritone,
+        7 => PerfectFifth,
+        8 => MinorSixth,
+        9 => MajorSixth,
+        10 => MinorSeventh,
+        11 => MajorSeventh,
+        12 => Octave,
+        s => Other(s),
+    }
+}
+
+/// Frequency ratio $2^{n/12}$ of an equal-tempered interval
+/// of `n` semitones. Negative intervals give ratios below 1.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::semitone_ratio;
+/// assert_eq!(semitone_ratio(12), 2.0);
+/// assert_eq!(semitone_ratio(-24), 0.25);
+/// assert_eq!(220.0 * semitone_ratio(7), 329.62756);
+/// ```
+pub fn semitone_ratio(n: i8) -> f32 {
+    semitone_ratio_fractional(n as f32)
+}
+
+/// Frequency ratio $2^{x/12}$ of an interval of a
+/// fractional number of semitones, as for detuning.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::semitone_ratio_fractional;
+/// assert_eq!(semitone_ratio_fractional(0.0), 1.0);
+/// assert_eq!((semitone_ratio_fractional(0.5) * 1000.0).round(), 1029.0);
+/// ```
+pub fn semitone_ratio_fractional(x: f32) -> f32 {
+    f32::powf(2.0, x / 12.0)
+}
+
+/// Approximate version of [semitone_ratio], using a
+/// Chebyshev series over one octave scaled by a power of
+/// two rather than `powf`. The accuracy is better than
+/// 0.01¢.
+pub fn semitone_ratio_approx(n: i8) -> f32 {
+    semitone_ratio_fractional_approx(n as f32)
+}
+
+/// Approximate version of [semitone_ratio_fractional],
+/// computed as [semitone_ratio_approx] is. The accuracy is
+/// better than 0.01¢ wherever the ratio is a normal `f32`.
+/// Ratios too large or too small for an `f32` give infinity
+/// or zero, as [semitone_ratio_fractional] does.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::semitone_ratio_fractional_approx;
+/// assert_eq!(semitone_ratio_fractional_approx(-24.0), 0.25);
+/// assert_eq!(semitone_ratio_fractional_approx(1600.0), f32::INFINITY);
+/// assert_eq!(semitone_ratio_fractional_approx(-2000.0), 0.0);
+/// ```
+pub fn semitone_ratio_fractional_approx(x: f32) -> f32 {
+    semitones_to_ratio_approx(x)
+}
+
+#[test]
+fn test_semitone_ratio() {
+    use crate::key_to_frequency_raw;
+
+    for n in i8::MIN..=i8::MAX {
+        let exact = f64::powf(2.0, n as f64 / 12.0);
+        let r = semitone_ratio(n) as f64;
+        assert!(f64::abs(r / exact - 1.0) < 1.0e-6, "{} {} {}", n, r, exact);
+        let a = semitone_ratio_approx(n) as f64;
+        let cents = 1200.0 * f64::log2(a / exact);
+        assert!(cents.abs() < 0.01, "{} {}", n, cents);
+    }
+    for i in -1200..=1200 {
+        let x = i as f32 / 100.0;
+        let r = semitone_ratio_fractional(x);
+        let a = semitone_ratio_fractional_approx(x);
+        let cents = 1200.0 * f32::log2(a / r);
+        assert!(cents.abs() < 0.01, "{} {}", x, cents);
+    }
+    // The extremes of the exponent range.
+    for i in -1800..=1600 {
+        let x = i as f32;
+        let r = semitone_ratio_fractional(x);
+        let a = semitone_ratio_fractional_approx(x);
+        if r.is_normal() {
+            let cents = 1200.0 * f64::log2(a as f64 / r as f64);
+            assert!(cents.abs() < 0.01, "{} {}", x, cents);
+        } else if r == 0.0 || r.is_infinite() {
+            assert_eq!(a, r, "{}", x);
+        } else {
+            assert!(f32::abs(a - r) <= 0.01 * r, "{} {} {}", x, r, a);
+        }
+    }
+    for x in [1.0e6, f32::INFINITY] {
+        assert_eq!(semitone_ratio_fractional_approx(x), f32::INFINITY);
+        assert_eq!(semitone_ratio_fractional_approx(-x), 0.0);
+    }
+    assert!(semitone_ratio_fractional_approx(f32::NAN).is_nan());
+
+    for key in 0..=115 {
+        let f = key_to_frequency_raw(key) * semitone_ratio(12);
+        assert!(f32::abs(f - key_to_frequency_raw(key + 12)) < 1.0e-6 * f);
+    }
+}
+
+#[test]
+fn test_intervals() {
+    assert_eq!(interval_semitones(0, 127), 127);
+    assert_eq!(interval_semitones(127, 0), -127);
+    assert_eq!(
+        classify_interval(interval_semitones(60, 72)),
+        IntervalName::Octave
+    );
+    assert_eq!(classify_interval(0), IntervalName::Unison);
+    assert_eq!(classify_interval(13), IntervalName::Other(13));
+
+    let fifth = frequency_ratio(60, 67);
+    assert!((fifth - 1.498).abs() < 0.001, "{}", fifth);
+    for key in 0..=115 {
+        let r = frequency_ratio(key, key + 12);
+        assert!((r - 2.0).abs() < 1.0e-5, "{} {}", key, r);
+    }
+}
+
+#[test]
+fn test_ratio_between_keys() {
+    for k1 in 0..=127 {
+        for k2 in 0..=127 {
+            let d = k2 as i32 - k1 as i32;
+            let r = ratio_between_keys(k1, k2);
+            if d % 12 == 0 {
+                assert_eq!(r, f32::powi(2.0, d / 12), "{} {}", k1, k2);
+            }
+            let exact = f64::powf(2.0, d as f64 / 12.0);
+            assert!(f64::abs(r as f64 / exact - 1.0) < 1.0e-6, "{} {}", k1, k2);
+        }
+    }
+    assert_eq!(ratio_between_keys(0, 120), 1024.0);
+    assert_eq!(ratio_between_keys(127, 7), 1.0 / 1024.0);
+}
+
+#[test]
+#[should_panic(expected = "key 128 out of range 0..=127")]
+fn test_interval_semitones_panic() {
+    interval_semitones(60, 128);
+}
+
+/// Transposes the given midi key by `semitones`, giving
+/// `None` if the result is not in the range `0..=127`. This
+/// is [transpose_checked] for the common case of a
+/// transposition that fits in an `i8`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::transpose;
+/// assert_eq!(transpose(60, -12), Some(48));
+/// assert_eq!(transpose(127, 1), None);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+#[inline]
+pub fn transpose(key: u8, semitones: i8) -> Option<u8> {
+    transpose_checked(key, semitones.into())
+}
+
+/// Transposes the given midi key by `semitones`, giving
+/// `None` if the result is not in the range `0..=127`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::transpose_checked;
+/// assert_eq!(transpose_checked(60, 7), Some(67));
+/// assert_eq!(transpose_checked(0, -1), None);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+#[inline]
+pub fn transpose_checked(key: u8, semitones: i16) -> Option<u8> {
+    let key = or_panic(check_key(key)) as i16;
+    key.checked_add(semitones)
+        .and_then(|k| u8::try_from(k).ok())
+        .filter(|&k| k <= 127)
+}
+
+/// Transposes the given midi key by `semitones`, clamping
+/// the result to the range `0..=127`. An `i8` offset, as
+/// taken by [transpose], converts with `.into()`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::transpose_saturating;
+/// assert_eq!(transpose_saturating(60, 7), 67);
+/// assert_eq!(transpose_saturating(120, 12), 127);
+/// assert_eq!(transpose_saturating(5, -12), 0);
+/// assert_eq!(transpose_saturating(0, i8::MIN.into()), 0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+#[inline]
+pub fn transpose_saturating(key: u8, semitones: i16) -> u8 {
+    let key = or_panic(check_key(key)) as i16;
+    key.saturating_add(semitones).clamp(0, 127) as u8
+}
+
+/// Transposes the given midi key by `semitones`, wrapping
+/// the result around the range `0..=127`: transposing key
+/// 127 up by one gives key 0. See also
+/// [transpose_wrapping_octave], which keeps the pitch
+/// class.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::transpose_wrapping;
+/// assert_eq!(transpose_wrapping(60, 7), 67);
+/// assert_eq!(transpose_wrapping(127, 1), 0);
+/// assert_eq!(transpose_wrapping(0, -1), 127);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+#[inline]
+pub fn transpose_wrapping(key: u8, semitones: i8) -> u8 {
+    let key = or_panic(check_key(key)) as i16 + semitones as i16;
+    key.rem_euclid(128) as u8
+}
+
+/// Transposes the given midi key by `semitones`, then moves
+/// a result outside the range `0..=127` by whole octaves to
+/// the nearest key in range with the same pitch class. This
+/// keeps a bass line that would fall off the bottom of the
+/// range in its key.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::transpose_wrapping_octave;
+/// assert_eq!(transpose_wrapping_octave(60, 7), 67);
+/// // B-2 is raised to B-1.
+/// assert_eq!(transpose_wrapping_octave(2, -3), 11);
+/// // A9 is lowered to A8.
+/// assert_eq!(transpose_wrapping_octave(120, 9), 117);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+#[inline]
+pub fn transpose_wrapping_octave(key: u8, semitones: i16) -> u8 {
+    let key = or_panic(check_key(key)) as i32 + semitones as i32;
+    let pitch_class = key.rem_euclid(12);
+    if key < 0 {
+        pitch_class as u8
+    } else if key > 127 {
+        // The highest key of each pitch class is in octave 9
+        // for C through G, and octave 8 otherwise.
+        (pitch_class + if pitch_class <= 7 { 120 } else { 108 }) as u8
+    } else {
+        key as u8
+    }
+}
+
+#[test]
+fn test_transpose() {
+    for key in 0..=127 {
+        for semitones in [
+            i16::MIN,
+            -300,
+            -128,
+            -127,
+            -13,
+            -12,
+            -1,
+            0,
+            1,
+            12,
+            13,
+            127,
+            128,
+            300,
+            i16::MAX,
+        ] {
+            let target = key as i32 + semitones as i32;
+            let in_range = (0..=127).contains(&target);
+
+            let checked = transpose_checked(key, semitones);
+            assert_eq!(checked, in_range.then_some(target as u8));
+
+            let saturating = transpose_saturating(key, semitones);
+            assert_eq!(saturating as i32, target.clamp(0, 127));
+
+            let wrapped = transpose_wrapping_octave(key, semitones);
+            assert!(wrapped <= 127);
+            assert_eq!((wrapped as i32 - target).rem_euclid(12), 0);
+            if in_range {
+                assert_eq!(wrapped as i32, target);
+            } else {
+                // Another octave would leave the range.
+                assert!(
+                    !(12..=115).contains(&wrapped),
+                    "{} {} {}",
+                    key,
+                    semitones,
+                    wrapped
+                );
+            }
+        }
+    }
+
+    assert_eq!(transpose_checked(0, -1), None);
+    assert_eq!(transpose_checked(127, 1), None);
+    assert_eq!(transpose_checked(127, -127), Some(0));
+    assert_eq!(transpose_checked(0, 128), None);
+    assert_eq!(transpose_checked(127, -128), None);
+    assert_eq!(transpose_checked(0, i16::MIN), None);
+
+    assert_eq!(transpose_saturating(0, -1), 0);
+    assert_eq!(transpose_saturating(127, 1), 127);
+    assert_eq!(transpose_saturating(0, 128), 127);
+    assert_eq!(transpose_saturating(127, -128), 0);
+    assert_eq!(transpose_saturating(127, i16::MIN), 0);
+
+    assert_eq!(transpose_wrapping_octave(0, -1), 11);
+    assert_eq!(transpose_wrapping_octave(127, 1), 116);
+    assert_eq!(transpose_wrapping_octave(0, 128), 116);
+    assert_eq!(transpose_wrapping_octave(127, -128), 11);
+    // -32768 is 4 above a multiple of 12, so an E.
+    assert_eq!(transpose_wrapping_octave(0, i16::MIN), 4);
+    // 32767 is 7 above a multiple of 12, so a G.
+    assert_eq!(transpose_wrapping_octave(0, i16::MAX), 127);
+}
+
+#[test]
+fn test_transpose_i8() {
+    for key in 0..=127 {
+        for semitones in i8::MIN..=i8::MAX {
+            let target = key as i32 + semitones as i32;
+            assert_eq!(
+                transpose(key, semitones),
+                transpose_checked(key, semitones.into())
+            );
+            assert_eq!(
+                transpose_saturating(key, semitones.into()) as i32,
+                target.clamp(0, 127)
+            );
+            assert_eq!(
+                transpose_wrapping(key, semitones) as i32,
+                target.rem_euclid(128)
+            );
+        }
+    }
+
+    assert_eq!(transpose(0, 0), Some(0));
+    assert_eq!(transpose(0, -1), None);
+    assert_eq!(transpose(0, 127), Some(127));
+    assert_eq!(transpose(0, i8::MIN), None);
+    assert_eq!(transpose(127, 0), Some(127));
+    assert_eq!(transpose(127, 1), None);
+    assert_eq!(transpose(127, i8::MIN), None);
+    assert_eq!(transpose(127, -127), Some(0));
+
+    assert_eq!(transpose_saturating(0, i8::MIN.into()), 0);
+    assert_eq!(transpose_saturating(0, i8::MAX.into()), 127);
+    assert_eq!(transpose_saturating(127, i8::MAX.into()), 127);
+    assert_eq!(transpose_saturating(127, i8::MIN.into()), 0);
+
+    assert_eq!(transpose_wrapping(0, -1), 127);
+    assert_eq!(transpose_wrapping(0, i8::MIN), 0);
+    assert_eq!(transpose_wrapping(0, i8::MAX), 127);
+    assert_eq!(transpose_wrapping(127, 1), 0);
+    assert_eq!(transpose_wrapping(127, i8::MAX), 126);
+    assert_eq!(transpose_wrapping(127, i8::MIN), 127);
+}
+
+#[test]
+#[should_panic(expected = "key 128 out of range 0..=127")]
+fn test_transpose_panic() {
+    transpose_checked(128, 0);
+}
+
+#[test]
+#[should_panic(expected = "key 128 out of range 0..=127")]
+fn test_transpose_wrapping_panic() {
+    transpose_wrapping(128, 0);
+}
@@ -0,0 +1,118 @@
+//! Iteration over ranges of keys.
+
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::key_to_frequency_raw;
+
+/// An iterator over a range of midi keys, yielding each key
+/// together with its frequency as computed by
+/// [key_to_frequency_raw].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::KeyFrequencyIter;
+/// let mut iter = KeyFrequencyIter::new(57..70).step_by(12);
+/// assert_eq!(iter.next(), Some((57, 220.0)));
+/// assert_eq!(iter.next(), Some((69, 440.0)));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyFrequencyIter {
+    keys: Range<u8>,
+}
+
+impl KeyFrequencyIter {
+    /// Make an iterator over the given range of keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range extends past key 127.
+    pub fn new(keys: Range<u8>) -> Self {
+        assert!(
+            keys.end <= 128 || keys.is_empty(),
+            "key range {:?} out of range 0..128",
+            keys,
+        );
+        KeyFrequencyIter { keys }
+    }
+}
+
+impl Iterator for KeyFrequencyIter {
+    type Item = (u8, f32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.keys.next()?;
+        Some((key, key_to_frequency_raw(key)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for KeyFrequencyIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let key = self.keys.next_back()?;
+        Some((key, key_to_frequency_raw(key)))
+    }
+}
+
+impl ExactSizeIterator for KeyFrequencyIter {}
+
+impl FusedIterator for KeyFrequencyIter {}
+
+/// Iterates over the keys from `start` up to but not
+/// including `end`, yielding each key together with its
+/// frequency.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_range_frequencies;
+/// // The C major scale from middle C.
+/// let scale: Vec<f32> = key_range_frequencies(60, 72)
+///     .filter(|&(k, _)| [0, 2, 4, 5, 7, 9, 11].contains(&(k % 12)))
+///     .map(|(_, f)| f)
+///     .collect();
+/// assert_eq!(scale.len(), 7);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `end` is greater than 128 and `start` is less
+/// than `end`.
+pub fn key_range_frequencies(start: u8, end: u8) -> KeyFrequencyIter {
+    KeyFrequencyIter::new(start..end)
+}
+
+#[test]
+fn test_key_frequency_iter() {
+    let iter = key_range_frequencies(0, 128);
+    assert_eq!(iter.len(), 128);
+    for (i, (k, f)) in iter.enumerate() {
+        assert_eq!(k as usize, i);
+        assert_eq!(f, key_to_frequency_raw(k));
+    }
+
+    let mut iter = key_range_frequencies(60, 63);
+    assert_eq!(iter.next_back(), Some((62, key_to_frequency_raw(62))));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.next(), Some((60, key_to_frequency_raw(60))));
+    assert_eq!(iter.next(), Some((61, key_to_frequency_raw(61))));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    let mut rev = key_range_frequencies(120, 128).rev();
+    assert_eq!(rev.next(), Some((127, key_to_frequency_raw(127))));
+
+    assert_eq!(key_range_frequencies(70, 60).len(), 0);
+    assert_eq!(key_range_frequencies(200, 200).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "key range 120..129 out of range 0..128")]
+fn test_key_frequency_iter_out_of_range() {
+    key_range_frequencies(120, 129);
+}
@@ -0,0 +1,198 @@
+//! A validated midi key type.
+
+use core::ops::{Add, Sub};
+
+use crate::{
+    KeyError, check_key, key_to_frequency_approx_raw, key_to_frequency_raw,
+    key_to_period_approx_raw, key_to_period_raw, transpose_checked, transpose_saturating,
+    transpose_wrapping_octave,
+};
+
+/// A midi key, guaranteed to be in the range `0..=127`.
+///
+/// Since a `Key` is always valid, its conversion methods
+/// never panic.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::Key;
+/// let a4 = Key::new(69).unwrap();
+/// assert_eq!(a4, Key::A4);
+/// assert_eq!(a4.frequency(), 440.0);
+/// assert_eq!(a4.pitch_class(), 9);
+/// assert_eq!(a4.octave(), 4);
+/// assert_eq!((a4 + 12).map(Key::frequency), Some(880.0));
+/// assert_eq!(a4 + 59, None);
+/// assert_eq!(Key::new(128), None);
+/// assert!(Key::try_from(128).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Key(u8);
+
+impl Key {
+    /// Lowest midi key, C-1 (key 0).
+    pub const MIN: Key = Key(0);
+    /// Highest midi key, G9 (key 127).
+    pub const MAX: Key = Key(127);
+    /// A0 (key 21), the lowest key of an 88-key piano.
+    pub const A0: Key = Key(21);
+    /// Middle C, C4 (key 60).
+    pub const C4: Key = Key(60);
+    /// A4 (key 69), the 440 Hz tuning reference.
+    pub const A4: Key = Key(69);
+    /// C8 (key 108), the highest key of an 88-key piano.
+    pub const C8: Key = Key(108);
+
+    /// Make a key from a midi key number, giving `None` if
+    /// it is not in the range `0..=127`.
+    pub const fn new(key: u8) -> Option<Key> {
+        if key <= 127 { Some(Key(key)) } else { None }
+    }
+
+    /// Pitch class of this key, from 0 for C to 11 for B.
+    pub const fn pitch_class(self) -> u8 {
+        self.0 % 12
+    }
+
+    /// Octave of this key in scientific pitch notation, from
+    /// -1 to 9. Middle C (key 60) is in octave 4.
+    pub const fn octave(self) -> i8 {
+        (self.0 / 12) as i8 - 1
+    }
+
+    /// Transposes this key by `semitones`, giving `None` if
+    /// the result is out of range. See [transpose_checked].
+    pub fn transpose_checked(self, semitones: i16) -> Option<Key> {
+        transpose_checked(self.0, semitones).map(Key)
+    }
+
+    /// Transposes this key by `semitones`, clamping the
+    /// result to the range of keys. See
+    /// [transpose_saturating].
+    pub fn transpose_saturating(self, semitones: i16) -> Key {
+        Key(transpose_saturating(self.0, semitones))
+    }
+
+    /// Transposes this key by `semitones`, moving a result
+    /// out of range by octaves back into range. See
+    /// [transpose_wrapping_octave].
+    pub fn transpose_wrapping_octave(self, semitones: i16) -> Key {
+        Key(transpose_wrapping_octave(self.0, semitones))
+    }
+
+    /// Frequency of this key. See [key_to_frequency_raw].
+    pub fn frequency(self) -> f32 {
+        key_to_frequency_raw(self.0)
+    }
+
+    /// Unit period of this key. See [key_to_period_raw].
+    pub fn period(self) -> f32 {
+        key_to_period_raw(self.0)
+    }
+
+    /// Approximate frequency of this key. See
+    /// [key_to_frequency_approx_raw].
+    pub fn frequency_approx(self) -> f32 {
+        key_to_frequency_approx_raw(self.0)
+    }
+
+    /// Approximate unit period of this key. See
+    /// [key_to_period_approx_raw].
+    pub fn period_approx(self) -> f32 {
+        key_to_period_approx_raw(self.0)
+    }
+}
+
+impl TryFrom<u8> for Key {
+    type Error = KeyError;
+
+    fn try_from(key: u8) -> Result<Self, Self::Error> {
+        check_key(key).map(Key)
+    }
+}
+
+impl From<Key> for u8 {
+    fn from(key: Key) -> Self {
+        key.0
+    }
+}
+
+/// Transposes a key up by the given number of semitones,
+/// giving `None` if the result is out of range.
+impl Add<i8> for Key {
+    type Output = Option<Key>;
+
+    fn add(self, semitones: i8) -> Self::Output {
+        let key = self.0 as i16 + semitones as i16;
+        u8::try_from(key).ok().and_then(|k| Key::try_from(k).ok())
+    }
+}
+
+/// Transposes a key down by the given number of semitones,
+/// giving `None` if the result is out of range.
+impl Sub<i8> for Key {
+    type Output = Option<Key>;
+
+    fn sub(self, semitones: i8) -> Self::Output {
+        let key = self.0 as i16 - semitones as i16;
+        u8::try_from(key).ok().and_then(|k| Key::try_from(k).ok())
+    }
+}
+
+#[test]
+fn test_key() {
+    for k in 0..=127 {
+        let key = Key::try_from(k).unwrap();
+        assert_eq!(u8::from(key), k);
+        assert_eq!(key.frequency(), key_to_frequency_raw(k));
+        assert_eq!(key.period(), key_to_period_raw(k));
+        assert_eq!(key.frequency_approx(), key_to_frequency_approx_raw(k));
+        assert_eq!(key.period_approx(), key_to_period_approx_raw(k));
+        assert_eq!(key.pitch_class(), k % 12);
+        assert_eq!(key.octave() as i16, k as i16 / 12 - 1);
+    }
+    for k in 128..=255 {
+        assert_eq!(Key::try_from(k), Err(KeyError::OutOfRange { key: k }));
+        assert_eq!(Key::new(k), None);
+    }
+    for k in 0..=127 {
+        assert_eq!(Key::new(k), Key::try_from(k).ok());
+    }
+
+    assert_eq!(Key::new(69).unwrap().frequency(), key_to_frequency_raw(69));
+    let named = [Key::MIN, Key::A0, Key::C4, Key::A4, Key::C8, Key::MAX];
+    assert_eq!(named.map(u8::from), [0, 21, 60, 69, 108, 127]);
+    assert!(named.windows(2).all(|w| w[0] < w[1]));
+    assert!(Key::new(61).unwrap() > Key::C4);
+
+    let c4 = Key::try_from(60).unwrap();
+    assert_eq!(c4.octave(), 4);
+    assert_eq!(Key::try_from(0).unwrap().octave(), -1);
+    assert_eq!(Key::try_from(127).unwrap().octave(), 9);
+    assert_eq!((c4 + 7).map(u8::from), Some(67));
+    assert_eq!((c4 - 7).map(u8::from), Some(53));
+    assert_eq!((c4 + -60).map(u8::from), Some(0));
+    assert_eq!(c4 + -61, None);
+    assert_eq!((c4 + 67).map(u8::from), Some(127));
+    assert_eq!(c4 + 68, None);
+    assert_eq!(c4 - i8::MIN, None);
+
+    for semitones in [i16::MIN, -128, -61, -1, 0, 1, 68, 128, i16::MAX] {
+        assert_eq!(
+            c4.transpose_checked(semitones).map(u8::from),
+            transpose_checked(60, semitones),
+        );
+        assert_eq!(
+            u8::from(c4.transpose_saturating(semitones)),
+            transpose_saturating(60, semitones),
+        );
+        assert_eq!(
+            u8::from(c4.transpose_wrapping_octave(semitones)),
+            transpose_wrapping_octave(60, semitones),
+        );
+    }
+    assert_eq!(Key::MIN.transpose_saturating(-1), Key::MIN);
+    assert_eq!(Key::MAX.transpose_checked(1), None);
+    assert_eq!(c4 + i8::MAX, None);
+}
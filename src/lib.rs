@@ -5,121 +5,1132 @@ frequency or period.
 
 There are two versions of these routines: "exact" versions
 with high precision and "approximate" versions. The
-approximate versions are "accurate enough". They may be
-slightly faster, and may take slightly less program memory —
-neither of these has been tested, though.
+approximate versions are "accurate enough". They are
+somewhat faster: on an x86-64 desktop, the `benches/`
+criterion suite measures about 15 ns per key for
+`key_to_frequency_approx` and `key_to_period_approx`,
+against about 19 ns for `key_to_frequency` and 23 ns for
+`key_to_period`. Run `cargo bench` to measure on your own
+target. The approximate versions may also take slightly
+less program memory; this has not been measured.
+
+Each routine returns `f32` by default. Routines with an
+`_f64` suffix return `f64` for applications needing more
+precision.
+
+Every key-to-frequency routine is strictly increasing in
+the key, and every key-to-period routine strictly
+decreasing, including across the octave boundaries used
+by the approximate versions. The `detail` module exposes
+the octave decomposition they use, for building custom
+approximations.
 
 The crate can be compiled `no_std` with
 `--no-default-features`. Otherwise the `std` feature will be
-used.
+used. Routines that allocate, such as
+`harmonic_series_frequencies` and the Scala file parsers
+`ScalaScale` and `KeyboardMapping`, need the `std` feature.
+
+With the `table-lookup` feature, `key_to_frequency`,
+`key_to_period` and their variants index into the
+precomputed `FREQUENCY_TABLE` and `PERIOD_TABLE` rather than
+calling `powf`. This is much faster on targets without a
+floating-point unit, at the cost of 1 KiB of read-only data
+for the two tables. With the `std` feature the results are
+identical either way.
+
+The `fixed-point` feature adds integer-only conversions such
+as `key_to_frequency_q16`, for targets with no floating-point
+hardware at all. Whole-Hz frequencies are always available
+from `key_to_frequency_hz_u16`, which just indexes a `u16`
+table.
+
+The Chebyshev coefficients and tables are checked in as
+generated source, so building needs no build-time
+computation. The `regen-consts` feature regenerates them
+and fails the build if they no longer match; it is only
+needed when changing the generator in `build.rs`.
 */
 
-#![no_std]
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use microcheby::ChebyshevExpansion as C;
+// Submodules import `num_traits::Float` for `powf` and
+// friends without `std`. Those imports are unused when a
+// dev-dependency links `std` anyway, hence their `allow`.
+#[cfg(not(feature = "std"))]
+pub use num_traits::float::*;
+
+#[rustfmt::skip]
+mod consts;
+
+#[test]
+fn test_chebyshev_docs() {
+    // Evaluate the series as documented, independently of
+    // `microcheby`.
+    fn eval(c: &[f32; CHEBYSHEV_ORDER], x: f64) -> f64 {
+        let t = 2.0 * x / 11.0 - 1.0;
+        let (mut t0, mut t1) = (1.0, t);
+        let mut f = c[0] as f64 + c[1] as f64 * t;
+        for &c in &c[2..] {
+            (t0, t1) = (t1, 2.0 * t * t1 - t0);
+            f += c as f64 * t1;
+        }
+        f
+    }
+    for x in 0..=11 {
+        let f = eval(&CHEBYSHEV_TOP_OCTAVE, x as f64);
+        let cents = 1200.0 * f64::log2(f / key_to_frequency_f64(116 + x));
+        assert!(cents.abs() < 0.12, "{} {}", x, cents);
+        let p = eval(&CHEBYSHEV_BOTTOM_OCTAVE, x as f64);
+        let cents = 1200.0 * f64::log2(key_to_period_f64(x) / p);
+        assert!(cents.abs() < 0.12, "{} {}", x, cents);
+    }
+}
+
+#[test]
+fn test_consts_finite() {
+    let series: &[&[f32]] = &[
+        &consts::CHEBYSHEV_TOP_OCTAVE,
+        &consts::CHEBYSHEV_BOTTOM_OCTAVE,
+        &consts::CHEBYSHEV_OCTAVE,
+        &consts::CHEBYSHEV_LOG_OCTAVE,
+        &consts::CHEBYSHEV_TOP_OCTAVE_3,
+        &consts::CHEBYSHEV_TOP_OCTAVE_6,
+        &consts::CHEBYSHEV_TOP_OCTAVE_HI,
+        &consts::CHEBYSHEV_BOTTOM_OCTAVE_HI,
+        &consts::FREQUENCY_TABLE,
+        &consts::PERIOD_TABLE,
+    ];
+    for s in series {
+        assert!(s.iter().all(|c| c.is_finite() && *c != 0.0));
+    }
+    let series: &[&[f64]] = &[
+        &consts::CHEBYSHEV_TOP_OCTAVE_F64,
+        &consts::CHEBYSHEV_BOTTOM_OCTAVE_F64,
+    ];
+    for s in series {
+        assert!(s.iter().all(|c| c.is_finite() && *c != 0.0));
+    }
+}
+
+mod batch;
+mod bend;
+mod cents;
+mod chord;
+pub mod detail;
+#[cfg(feature = "fixed-point")]
+mod fixed;
+mod harmonic;
+mod interval;
+mod iter;
+mod key;
+mod midi2;
+mod mts;
+mod note;
+mod perceptual;
+mod sample;
+#[cfg(feature = "std")]
+mod scala;
+mod scale;
+mod tuning;
+mod units;
+mod wave;
+
+use detail::{try_key_to_params_bottom, try_key_to_params_top};
+
+pub use batch::{batch_key_to_frequency, batch_key_to_period};
+pub use bend::{
+    BEND_CENTER, DEFAULT_BEND_RANGE, MPE_NOTE_BEND_RANGE, MpePitch, key_bend_to_frequency,
+    key_bend_to_frequency_approx, key_bend_to_frequency_checked, key_bend_to_frequency_default,
+};
+pub use cents::{
+    acoustic_beat_frequency, beat_frequency_with_detuning, cents_between, cents_to_frequency,
+    frequency_to_cents, key_plus_cents_to_frequency, key_plus_cents_to_frequency_approx,
+    key_to_cents, key_to_frequency_detuned, key_to_frequency_detuned_approx,
+};
+pub use chord::{ChordType, MAX_CHORD_NOTES, chord_frequencies, chord_frequencies_approx};
+pub use consts::{
+    CHEBYSHEV_BOTTOM_OCTAVE, CHEBYSHEV_ORDER, CHEBYSHEV_TOP_OCTAVE, FREQUENCY_HZ_TABLE,
+    FREQUENCY_TABLE, PERIOD_TABLE,
+};
+#[cfg(feature = "fixed-point")]
+pub use fixed::{
+    key_to_frequency_q16, key_to_period_q16, key_to_period_q24, try_key_to_frequency_q16,
+    try_key_to_period_q16, try_key_to_period_q24,
+};
+#[cfg(feature = "std")]
+pub use harmonic::harmonic_series_frequencies;
+pub use harmonic::{harmonic_series_frequencies_into, harmonic_to_nearest_key};
+pub use interval::{
+    IntervalName, classify_interval, frequency_ratio, interval_semitones, ratio_between_keys,
+    semitone_ratio, semitone_ratio_approx, semitone_ratio_fractional,
+    semitone_ratio_fractional_approx, transpose, transpose_checked, transpose_saturating,
+    transpose_wrapping, transpose_wrapping_octave,
+};
+pub use iter::{KeyFrequencyIter, key_range_frequencies};
+pub use key::Key;
+pub use midi2::{
+    frequency_to_pitch_7_9, frequency_to_pitch_7_9_approx, key_cents_to_pitch_7_9,
+    midi2_note_to_frequency, midi2_note_to_frequency_f64, pitch_7_9_to_frequency,
+    pitch_7_9_to_frequency_approx,
+};
+pub use mts::{MTS_NO_CHANGE, frequency_to_mts_bytes, mts_bytes_to_frequency};
+#[cfg(feature = "std")]
+pub use note::key_to_helmholtz;
+pub use note::{
+    AccidentalPreference, KeyColor, MAX_NOTE_NAME_LEN, NameError, ParseNoteError, PitchClass,
+    Spelling, is_black_key, is_white_key, key_to_name, key_to_name_spelled, key_to_note_name,
+    key_to_note_name_buf, key_to_note_name_flat, key_to_note_name_with_preference,
+    key_to_pitch_class_octave, octave_fold, parse_helmholtz, parse_note_name,
+    parse_note_name_german, piano_key_color, piano_key_index, piano_white_key_index, pitch_class,
+    pitch_class_octave_to_key,
+};
+pub use perceptual::{
+    bark_to_frequency, frequency_to_bark, frequency_to_mel, key_to_bark, key_to_mel,
+    mel_to_frequency, mel_to_key,
+};
+pub use sample::{
+    angular_frequency_to_key, key_to_angular_frequency, key_to_angular_frequency_approx,
+    key_to_normalized_angular_frequency, key_to_normalized_angular_frequency_approx,
+    key_to_period_samples, key_to_period_samples_approx, key_to_period_samples_rounded,
+    key_to_phase_increment, key_to_phase_increment_approx, key_to_phase_increment_fixed,
+    key_to_phase_increment_fixed_f64, key_to_phase_increment_q32,
+};
+#[cfg(feature = "std")]
+pub use scala::{KeyboardMapping, ScalaError, ScalaScale};
+#[cfg(feature = "std")]
+pub use scale::scale_frequencies;
+pub use scale::{
+    BLUES_SCALE, CHROMATIC_SCALE, HARMONIC_MINOR_SCALE, MAJOR_PENTATONIC_SCALE, MAJOR_SCALE,
+    MELODIC_MINOR_SCALE, MINOR_PENTATONIC_SCALE, MINOR_SCALE, scale_keys,
+};
+pub use tuning::{
+    ConcertPitch, Edo, EqualTemperament, JUST_RATIOS, JustIntonation, Meantone, PythagoreanTuning,
+    QuarterCommaMeantone, StretchTuning, Tuning, TuningSystem, TuningTable, WerckmeisterIII,
+    key_to_frequency_approx_with_reference, key_to_frequency_with_reference,
+    key_to_period_approx_with_reference, key_to_period_with_reference,
+};
+pub use units::{Frequency, Period};
+pub use wave::{SPEED_OF_SOUND, key_to_wavelength, key_to_wavelength_default, key_to_wavenumber};
+
+/// Midi key of A4, the 440 Hz tuning reference.
+pub const MIDI_A4: u8 = 69;
+/// Midi key of C4, middle C.
+pub const MIDI_C4: u8 = 60;
+/// Midi key of C0, the lowest C in scientific pitch
+/// notation with a non-negative octave.
+pub const MIDI_C0: u8 = 12;
+/// Midi key of middle C, C4.
+pub const MIDI_MIDDLE_C: u8 = MIDI_C4;
+
+/// Frequency in Hz of A4, the tuning reference.
+pub const FREQ_A4: f32 = 440.0;
+/// Frequency in Hz of middle C, C4, rounded to three
+/// decimal places. [key_to_frequency] gives the nearest
+/// `f32` instead.
+pub const FREQ_C4: f32 = 261.626;
+
+#[test]
+fn test_named_constants() {
+    assert_eq!(key_to_frequency_raw(MIDI_A4), FREQ_A4);
+    assert!(f32::abs(key_to_frequency_raw(MIDI_C4) - FREQ_C4) < 0.0005);
+    assert_eq!(MIDI_MIDDLE_C, MIDI_C4);
+    assert_eq!(MIDI_C4 - MIDI_C0, 48);
+    assert_eq!(u8::from(Key::A4), MIDI_A4);
+    assert_eq!(u8::from(Key::C4), MIDI_C4);
+    assert_eq!(key_to_note_name(MIDI_C0), "C0");
+}
+
+/// Error returned by the fallible conversion routines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyError {
+    /// The given key is not in the range `0..=127`.
+    OutOfRange {
+        /// The offending key.
+        key: u8,
+    },
+}
+
+impl core::fmt::Display for KeyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            KeyError::OutOfRange { key } => write!(f, "key {} out of range 0..=127", key),
+        }
+    }
+}
+
+impl core::error::Error for KeyError {}
+
+/// Unwrap the result of a fallible conversion, panicking
+/// with the error's message on failure.
+fn or_panic<T>(result: Result<T, KeyError>) -> T {
+    match result {
+        Ok(v) => v,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+/// Check that `key` is in the range `0..=127`.
+fn check_key(key: u8) -> Result<u8, KeyError> {
+    if key >= 128 {
+        return Err(KeyError::OutOfRange { key });
+    }
+    Ok(key)
+}
+
+/// Directly computes the frequency for a given midi key value $k$,
+/// using the formula
+///    $$440 \cdot 2^{\frac{k - 69}{12}}$$
+/// for $k$ in $[0..127]$.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{key_to_frequency, Frequency};
+/// assert_eq!(key_to_frequency(69), Frequency(440.0));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_frequency(key: u8) -> Frequency {
+    Frequency(key_to_frequency_raw(key))
+}
+
+/// Like [key_to_frequency], but returns the frequency in Hz
+/// as a bare `f32`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_frequency_raw;
+/// assert_eq!(key_to_frequency_raw(69).round(), 440.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_frequency_raw(key: u8) -> f32 {
+    or_panic(try_key_to_frequency(key))
+}
+
+/// Like [key_to_frequency], but returns an error rather than
+/// panicking if `key` is not in the range `0..=127`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{try_key_to_frequency, KeyError};
+/// assert_eq!(try_key_to_frequency(69).map(f32::round), Ok(440.0));
+/// assert_eq!(try_key_to_frequency(128), Err(KeyError::OutOfRange { key: 128 }));
+/// ```
+pub fn try_key_to_frequency(key: u8) -> Result<f32, KeyError> {
+    let key = check_key(key)?;
+    Ok(key_to_frequency_valid(key))
+}
+
+/// Like [key_to_frequency], but returns `None` rather than
+/// panicking if `key` is not in the range `0..=127`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_frequency_checked;
+/// assert_eq!(key_to_frequency_checked(69).map(f32::round), Some(440.0));
+/// assert_eq!(key_to_frequency_checked(128), None);
+/// ```
+pub fn key_to_frequency_checked(key: u8) -> Option<f32> {
+    try_key_to_frequency(key).ok()
+}
+
+/// Like [key_to_frequency], but clamps `key` to the range
+/// `0..=127` rather than panicking: any key above 127 gives
+/// the frequency of key 127. The result is always finite
+/// and positive.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{key_to_frequency_raw, key_to_frequency_saturating};
+/// assert_eq!(key_to_frequency_saturating(255), key_to_frequency_raw(127));
+/// ```
+pub fn key_to_frequency_saturating(key: u8) -> f32 {
+    key_to_frequency_valid(key.min(127))
+}
+
+/// Frequency of a key already known to be in range.
+#[cfg(not(feature = "table-lookup"))]
+fn key_to_frequency_valid(key: u8) -> f32 {
+    fractional_key_to_frequency(key as f32)
+}
+
+/// Frequency of a key already known to be in range, looked
+/// up in [FREQUENCY_TABLE].
+#[cfg(feature = "table-lookup")]
+fn key_to_frequency_valid(key: u8) -> f32 {
+    FREQUENCY_TABLE[key as usize]
+}
+
+/// Looks up the frequency for a given midi key in
+/// [FREQUENCY_TABLE], which is precomputed at build time.
+/// The result is identical to that of [key_to_frequency].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_frequency_table;
+/// assert_eq!(key_to_frequency_table(69), 440.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_frequency_table(key: u8) -> f32 {
+    FREQUENCY_TABLE[or_panic(check_key(key)) as usize]
+}
+
+#[test]
+fn test_key_to_frequency_table() {
+    for k in 0..=127 {
+        assert_eq!(key_to_frequency_table(k), key_to_frequency_raw(k));
+    }
+}
+
+/// Looks up the frequency for a given midi key rounded to
+/// the nearest whole Hz in [FREQUENCY_HZ_TABLE], which is
+/// precomputed at build time. This needs no floating-point
+/// arithmetic at all. Every key from 0 (8 Hz) to 127
+/// (12544 Hz) fits in a `u16`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_frequency_hz_u16;
+/// assert_eq!(key_to_frequency_hz_u16(69), 440);
+/// assert_eq!(key_to_frequency_hz_u16(60), 262);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_frequency_hz_u16(key: u8) -> u16 {
+    FREQUENCY_HZ_TABLE[or_panic(check_key(key)) as usize]
+}
+
+#[test]
+fn test_key_to_frequency_hz_u16() {
+    for k in 0..=127 {
+        assert_eq!(
+            key_to_frequency_hz_u16(k),
+            key_to_frequency_raw(k).round() as u16
+        );
+    }
+    assert_eq!(key_to_frequency_hz_u16(0), 8);
+    assert_eq!(key_to_frequency_hz_u16(127), 12544);
+}
+
+#[test]
+#[should_panic(expected = "key 128 out of range 0..=127")]
+fn test_key_to_frequency_hz_u16_out_of_range() {
+    key_to_frequency_hz_u16(128);
+}
+
+/// Looks up the frequency for a given midi key in
+/// [FREQUENCY_TABLE] as [key_to_frequency_table] does, but
+/// as a `const fn`, so that frequencies can be computed at
+/// compile time and stored in flash.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_frequency_const;
+/// const FREQ_A4: f32 = key_to_frequency_const(69);
+/// assert_eq!(FREQ_A4, 440.0);
+/// ```
+///
+/// An out-of-range key in a constant fails to compile:
+///
+/// ```compile_fail
+/// # use keytones::key_to_frequency_const;
+/// const FREQ: f32 = key_to_frequency_const(128);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`. In a
+/// constant this is a compile-time error.
+pub const fn key_to_frequency_const(key: u8) -> f32 {
+    assert!(key <= 127, "key out of range 0..=127");
+    FREQUENCY_TABLE[key as usize]
+}
+
+#[test]
+fn test_key_to_frequency_const() {
+    const FREQ_A4: f32 = key_to_frequency_const(69);
+    const CHORD: [f32; 3] = [
+        key_to_frequency_const(60),
+        key_to_frequency_const(64),
+        key_to_frequency_const(67),
+    ];
+    assert_eq!(FREQ_A4, 440.0);
+    assert_eq!(CHORD, [60, 64, 67].map(key_to_frequency_raw));
+    for k in 0..=127 {
+        assert_eq!(key_to_frequency_const(k), key_to_frequency_raw(k));
+    }
+}
+
+/// Directly computes the frequency for a fractional midi
+/// key value $k$, using the same formula as
+/// [key_to_frequency]. This is useful for glides and other
+/// continuous pitch changes. At integer keys the result is
+/// identical to that of [key_to_frequency].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_frequency_fractional;
+/// assert_eq!(key_to_frequency_fractional(69.0), 440.0);
+/// assert_eq!(key_to_frequency_fractional(68.5).round(), 427.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0.0..=127.0`.
+pub fn key_to_frequency_fractional(key: f32) -> f32 {
+    assert!(
+        (0.0..=127.0).contains(&key),
+        "fractional key {} out of range 0.0..=127.0",
+        key,
+    );
+    fractional_key_to_frequency(key)
+}
+
+/// Frequency of a fractional key, without range checking.
+fn fractional_key_to_frequency(key: f32) -> f32 {
+    440.0 * f32::powf(2.0, (key - 69.0) / 12.0)
+}
+
+/// Computes the approximate frequency for a fractional midi
+/// key value by linear interpolation between adjacent
+/// entries of [FREQUENCY_TABLE]. This needs no
+/// transcendental functions at all.
+///
+/// Since frequency is exponential in key, the interpolated
+/// frequency is always slightly sharp between keys, by at
+/// most about 0.72¢ near the middle of each semitone. For
+/// better accuracy at somewhat higher cost, use
+/// [key_to_frequency_fractional_approx].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_frequency_lerp;
+/// assert_eq!(key_to_frequency_lerp(69.0), 440.0);
+/// assert_eq!(key_to_frequency_lerp(68.5).round(), 428.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0.0..=127.0`.
+pub fn key_to_frequency_lerp(key: f32) -> f32 {
+    assert!(
+        (0.0..=127.0).contains(&key),
+        "fractional key {} out of range 0.0..=127.0",
+        key,
+    );
+    let i = key as usize;
+    // Key 127 is the end of the last segment.
+    if i == 127 {
+        return FREQUENCY_TABLE[127];
+    }
+    let t = key - i as f32;
+    let (f0, f1) = (FREQUENCY_TABLE[i], FREQUENCY_TABLE[i + 1]);
+    f0 + t * (f1 - f0)
+}
+
+#[test]
+fn test_key_to_frequency_lerp() {
+    let mut worst: f32 = 0.0;
+    for i in 0..=12700 {
+        let k = i as f32 / 100.0;
+        let f = key_to_frequency_fractional(k);
+        let g = key_to_frequency_lerp(k);
+        let cents = 1200.0 * f32::log2(g / f);
+        assert!(cents > -0.001, "{} {}", k, cents);
+        worst = worst.max(cents);
+    }
+    assert!(worst > 0.71 && worst < 0.73, "{}", worst);
+    assert_eq!(key_to_frequency_lerp(127.0), key_to_frequency_raw(127));
+}
+
+#[test]
+fn test_key_to_frequency_fractional() {
+    for k in 0..=127 {
+        assert_eq!(
+            key_to_frequency_fractional(k as f32),
+            key_to_frequency_raw(k)
+        );
+    }
+    for k in 0..127 {
+        let lo = key_to_frequency_raw(k);
+        let hi = key_to_frequency_raw(k + 1);
+        let mid = key_to_frequency_fractional(k as f32 + 0.5);
+        assert!(lo < mid && mid < hi, "{} {} {} {}", k, lo, mid, hi);
+        let gmean = f32::sqrt(lo * hi);
+        assert!(
+            f32::abs(mid - gmean) < 1.0e-5 * gmean,
+            "{} {} {}",
+            k,
+            mid,
+            gmean
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "fractional key 127.5 out of range 0.0..=127.0")]
+fn test_key_to_frequency_fractional_panic() {
+    key_to_frequency_fractional(127.5);
+}
+
+/// Directly computes the "unit period" for a given midi key value $k$,
+/// the inverse of frequency. This has units of
+/// $$\frac{\text{seconds}}{\text{cycle}}$$
+/// and can thus be multiplied by a sample rate in
+/// $$\frac{\text{samples}}{\text{second}}$$
+/// to get a cycle period in samples.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period;
+/// assert_eq!((key_to_period(69) * 440.0).0.round(), 1.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_period(key: u8) -> Period {
+    Period(key_to_period_raw(key))
+}
+
+/// Like [key_to_period], but returns the period in seconds
+/// as a bare `f32`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period_raw;
+/// assert_eq!((key_to_period_raw(69) * 440.0).round(), 1.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_period_raw(key: u8) -> f32 {
+    or_panic(try_key_to_period(key))
+}
+
+/// Like [key_to_period], but returns an error rather than
+/// panicking if `key` is not in the range `0..=127`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{try_key_to_period, KeyError};
+/// assert_eq!(try_key_to_period(69).map(|p| (p * 440.0).round()), Ok(1.0));
+/// assert_eq!(try_key_to_period(128), Err(KeyError::OutOfRange { key: 128 }));
+/// ```
+pub fn try_key_to_period(key: u8) -> Result<f32, KeyError> {
+    let key = check_key(key)?;
+    Ok(key_to_period_valid(key))
+}
+
+/// Unit period of a key already known to be in range.
+#[cfg(not(feature = "table-lookup"))]
+fn key_to_period_valid(key: u8) -> f32 {
+    1.0 / key_to_frequency_valid(key)
+}
+
+/// Unit period of a key already known to be in range, looked
+/// up in [PERIOD_TABLE].
+#[cfg(feature = "table-lookup")]
+fn key_to_period_valid(key: u8) -> f32 {
+    PERIOD_TABLE[key as usize]
+}
+
+/// Like [key_to_period], but returns `None` rather than
+/// panicking if `key` is not in the range `0..=127`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period_checked;
+/// assert_eq!(key_to_period_checked(69).map(|p| (p * 440.0).round()), Some(1.0));
+/// assert_eq!(key_to_period_checked(128), None);
+/// ```
+pub fn key_to_period_checked(key: u8) -> Option<f32> {
+    try_key_to_period(key).ok()
+}
+
+/// Looks up the unit period for a given midi key in
+/// [PERIOD_TABLE], which is precomputed at build time.
+/// The result is identical to that of [key_to_period].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period_table;
+/// assert_eq!(key_to_period_table(69), 1.0 / 440.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_period_table(key: u8) -> f32 {
+    PERIOD_TABLE[or_panic(check_key(key)) as usize]
+}
+
+#[test]
+fn test_key_to_period_table() {
+    for k in 0..=127 {
+        assert_eq!(key_to_period_table(k), key_to_period_raw(k));
+    }
+}
+
+/// Looks up the unit period for a given midi key in
+/// [PERIOD_TABLE] as [key_to_period_table] does, but as a
+/// `const fn`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period_const;
+/// const PERIOD_A4: f32 = key_to_period_const(69);
+/// assert_eq!(PERIOD_A4, 1.0 / 440.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`. In a
+/// constant this is a compile-time error.
+pub const fn key_to_period_const(key: u8) -> f32 {
+    assert!(key <= 127, "key out of range 0..=127");
+    PERIOD_TABLE[key as usize]
+}
+
+#[test]
+fn test_key_to_period_const() {
+    const PERIOD_A4: f32 = key_to_period_const(69);
+    assert_eq!(PERIOD_A4, 1.0 / 440.0);
+    for k in 0..=127 {
+        assert_eq!(key_to_period_const(k), key_to_period_raw(k));
+    }
+}
+
+#[test]
+#[should_panic(expected = "key out of range 0..=127")]
+fn test_key_to_frequency_const_panic() {
+    key_to_frequency_const(core::hint::black_box(128));
+}
+
+/// Directly computes the unit period for a fractional midi
+/// key value, the inverse of [key_to_frequency_fractional].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period_fractional;
+/// assert_eq!((key_to_period_fractional(69.0) * 440.0).round(), 1.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0.0..=127.0`.
+pub fn key_to_period_fractional(key: f32) -> f32 {
+    1.0 / key_to_frequency_fractional(key)
+}
+
+/// Like [key_to_period], but clamps `key` to the range
+/// `0..=127` rather than panicking: any key above 127 gives
+/// the period of key 127. The result is always finite and
+/// positive.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{key_to_period_raw, key_to_period_saturating};
+/// assert_eq!(key_to_period_saturating(255), key_to_period_raw(127));
+/// ```
+pub fn key_to_period_saturating(key: u8) -> f32 {
+    1.0 / key_to_frequency_saturating(key)
+}
+
+#[test]
+fn test_saturating() {
+    for k in 0..=255 {
+        let f = key_to_frequency_saturating(k);
+        let p = key_to_period_saturating(k);
+        assert!(f.is_finite() && f > 0.0);
+        assert!(p.is_finite() && p > 0.0);
+        assert_eq!(f, key_to_frequency_raw(k.min(127)));
+        assert_eq!(p, key_to_period_raw(k.min(127)));
+    }
+}
+
+/// Double-precision version of [key_to_frequency].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_frequency_f64;
+/// assert_eq!(key_to_frequency_f64(69), 440.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_frequency_f64(key: u8) -> f64 {
+    or_panic(try_key_to_frequency_f64(key))
+}
+
+/// Double-precision version of [try_key_to_frequency].
+pub fn try_key_to_frequency_f64(key: u8) -> Result<f64, KeyError> {
+    let key = check_key(key)?;
+    Ok(440.0 * f64::powf(2.0, (key as f64 - 69.0) / 12.0))
+}
+
+/// Double-precision version of [key_to_period].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period_f64;
+/// assert_eq!(key_to_period_f64(69) * 440.0, 1.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_period_f64(key: u8) -> f64 {
+    or_panic(try_key_to_period_f64(key))
+}
+
+/// Double-precision version of [try_key_to_period].
+pub fn try_key_to_period_f64(key: u8) -> Result<f64, KeyError> {
+    try_key_to_frequency_f64(key).map(|f| 1.0 / f)
+}
+
+#[test]
+fn test_key_to_frequency_f64() {
+    // Reference values from the standard MIDI tuning table.
+    let tests: &[(u8, f64)] = &[
+        (0, 8.175798915643707),
+        (21, 27.5),
+        (60, 261.6255653005986),
+        (69, 440.0),
+        (108, 4186.009044809578),
+        (127, 12543.853951415975),
+    ];
+    for &(key, freq) in tests {
+        let f = key_to_frequency_f64(key);
+        assert!(f64::abs(f - freq) < 1.0e-9 * freq, "{} {} {}", key, f, freq);
+        let p = key_to_period_f64(key);
+        assert!(f64::abs(p * freq - 1.0) < 1.0e-9, "{} {} {}", key, p, freq);
+    }
+}
+
+/// Evaluate a double-precision Chebyshev series over
+/// `[0..11]` at `x` using the Clenshaw recurrence. The
+/// zeroth coefficient is pre-halved, as in `microcheby`.
+fn eval_f64<const N: usize>(coeffs: &[f64; N], x: f64) -> f64 {
+    let x_rel_2 = -2.0 + x * (4.0 / 11.0);
+    let mut d = 0.0;
+    let mut dd = 0.0;
+    for &c in coeffs[1..].iter().rev() {
+        let temp = d;
+        d = x_rel_2 * d - dd + c;
+        dd = temp;
+    }
+    0.5 * x_rel_2 * d - dd + coeffs[0]
+}
+
+/// Scale factor $2^{-o}$ for `o` octaves down, constructed
+/// directly from its exponent bits so that octave scaling
+/// is exact.
+fn octave_scale(o: u8) -> f32 {
+    f32::from_bits((127 - o as u32) << 23)
+}
+
+/// Double-precision version of [octave_scale].
+fn octave_scale_f64(o: u8) -> f64 {
+    f64::from_bits((1023 - o as u64) << 52)
+}
+
+/// Computes the approximate frequency for a given midi key
+/// value using a formula involving a Chebyshev series. (See
+/// the source code for details.) The accuracy is better
+/// than 0.12¢; run `cargo run --example accuracy` for
+/// measured errors. Every A is exact, so that A4 (key 69)
+/// is exactly 440 Hz.
+///
+/// Octaves are exact: the result for key `k + 12` is
+/// always exactly twice that for key `k`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{key_to_frequency_approx, Frequency};
+/// assert_eq!(key_to_frequency_approx(69), Frequency(440.0));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_frequency_approx(key: u8) -> Frequency {
+    Frequency(key_to_frequency_approx_raw(key))
+}
+
+/// Like [key_to_frequency_approx], but returns the
+/// frequency in Hz as a bare `f32`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_frequency_approx_raw;
+/// assert_eq!(key_to_frequency_approx_raw(69), 440.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_frequency_approx_raw(key: u8) -> f32 {
+    or_panic(try_key_to_frequency_approx(key))
+}
+
+/// Like [key_to_frequency_approx], but returns an error rather
+/// than panicking if `key` is not in the range `0..=127`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{try_key_to_frequency_approx, KeyError};
+/// assert_eq!(try_key_to_frequency_approx(69).map(f32::round), Ok(440.0));
+/// assert_eq!(try_key_to_frequency_approx(128), Err(KeyError::OutOfRange { key: 128 }));
+/// ```
+pub fn try_key_to_frequency_approx(key: u8) -> Result<f32, KeyError> {
+    let (m, o) = try_key_to_params_top(key)?;
+    let approx = C::const_new(0.0, 4.0 / 11.0, consts::CHEBYSHEV_TOP_OCTAVE);
+    // Key 117 is A8. Pinning it pins every A, so that A4 is
+    // exactly 440 Hz.
+    let f = if m == 1 {
+        7040.0
+    } else {
+        approx.eval_4(m as f32)
+    };
+    let p = octave_scale(o);
 
-use microcheby::ChebyshevExpansion as C;
-#[cfg(not(feature = "std"))]
-pub use num_traits::float::*;
+    Ok(f * p)
+}
 
-mod consts {
-    include!(concat!(env!("OUT_DIR"), "/consts.rs"));
+/// Like [key_to_frequency_approx], but returns `None` rather
+/// than panicking if `key` is not in the range `0..=127`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_frequency_approx_checked;
+/// assert_eq!(key_to_frequency_approx_checked(69).map(f32::round), Some(440.0));
+/// assert_eq!(key_to_frequency_approx_checked(128), None);
+/// ```
+pub fn key_to_frequency_approx_checked(key: u8) -> Option<f32> {
+    try_key_to_frequency_approx(key).ok()
 }
 
-/// Directly computes the frequency for a given midi key value $k$,
-/// using the formula
-///    $$440 \cdot 2^{\frac{k - 69}{12}}$$
-/// for $k$ in $[0..127]$.
+/// Computes the approximate frequency for a given midi key
+/// value as [key_to_frequency_approx] does, but using a
+/// higher-order Chebyshev series. The worst-case error
+/// over all keys is about 0.00012¢, at the cost of a larger
+/// coefficient table and a few more multiplies.
 ///
 /// # Examples
 ///
 /// ```
-/// # use keytones::key_to_frequency;
-/// assert_eq!(key_to_frequency(69).round(), 440.0);
+/// # use keytones::key_to_frequency_approx_hi;
+/// assert_eq!(key_to_frequency_approx_hi(69).round(), 440.0);
 /// ```
 ///
 /// # Panics
 ///
 /// Panics if `key` is not in the range `0..=127`.
-pub fn key_to_frequency(key: u8) -> f32 {
-    assert!(key < 128);
-    440.0 * f32::powf(2.0, (key as f32 - 69.0) / 12.0)
+pub fn key_to_frequency_approx_hi(key: u8) -> f32 {
+    or_panic(try_key_to_frequency_approx_hi(key))
 }
 
-/// Directly computes the "unit period" for a given midi key value $k$,
-/// the inverse of frequency. This has units of
-/// $$\frac{\text{seconds}}{\text{cycle}}$$
-/// and can thus be multiplied by a sample rate in
-/// $$\frac{\text{samples}}{\text{second}}$$
-/// to get a cycle period in samples.
+/// Like [key_to_frequency_approx_hi], but returns an error
+/// rather than panicking if `key` is not in the range
+/// `0..=127`.
+pub fn try_key_to_frequency_approx_hi(key: u8) -> Result<f32, KeyError> {
+    let (m, o) = try_key_to_params_top(key)?;
+    let approx = C::const_new(0.0, 4.0 / 11.0, consts::CHEBYSHEV_TOP_OCTAVE_HI);
+    let f = approx.eval(m as f32);
+    let p = octave_scale(o);
+
+    Ok(f * p)
+}
+
+#[test]
+fn test_key_to_frequency_approx_hi() {
+    let mut max_cents: f64 = 0.0;
+    for key in 0..=127 {
+        let f = key_to_frequency_approx_hi(key) as f64;
+        let cents = 1200.0 * f64::log2(f / key_to_frequency_f64(key));
+        max_cents = max_cents.max(cents.abs());
+    }
+    assert!(max_cents < 0.001, "{}", max_cents);
+}
+
+/// Computes the approximate frequency for a given midi key
+/// value as [key_to_frequency_approx] does, but with the
+/// order of the Chebyshev series chosen by `ORDER`. Higher
+/// orders trade coefficient table size and multiplies for
+/// accuracy:
+///
+/// | `ORDER` | worst-case error |
+/// |---------|------------------|
+/// | 3       | 3¢               |
+/// | 4       | 0.12¢            |
+/// | 6       | 0.0012¢          |
+/// | 8       | 0.00012¢         |
+///
+/// Order 4 gives exactly the results of
+/// [key_to_frequency_approx], and order 8 those of
+/// [key_to_frequency_approx_hi]. Only the coefficient table
+/// for the chosen order is linked in.
 ///
 /// # Examples
 ///
 /// ```
-/// # use keytones::key_to_period;
-/// assert_eq!((key_to_period(69) * 440.0).round(), 1.0);
+/// # use keytones::key_to_frequency_approx_n;
+/// assert_eq!(key_to_frequency_approx_n::<6>(69).round(), 440.0);
+/// ```
+///
+/// Unsupported orders are rejected at compile time.
+///
+/// ```compile_fail
+/// # use keytones::key_to_frequency_approx_n;
+/// key_to_frequency_approx_n::<5>(69);
 /// ```
 ///
 /// # Panics
 ///
 /// Panics if `key` is not in the range `0..=127`.
-pub fn key_to_period(key: u8) -> f32 {
-    1.0 / key_to_frequency(key)
+pub fn key_to_frequency_approx_n<const ORDER: usize>(key: u8) -> f32 {
+    or_panic(try_key_to_frequency_approx_n::<ORDER>(key))
 }
 
-fn key_to_params_top(key: u8) -> (u8, u8) {
-    assert!(key < 128);
-    let m = (key + 120 - 116) % 12;
-    let o = 10 - (key + 12 - m) / 12;
-    (m, o)
+/// Like [key_to_frequency_approx_n], but returns an error
+/// rather than panicking if `key` is not in the range
+/// `0..=127`.
+pub fn try_key_to_frequency_approx_n<const ORDER: usize>(key: u8) -> Result<f32, KeyError> {
+    const {
+        assert!(
+            matches!(ORDER, 3 | 4 | 6 | 8),
+            "approximation order must be 3, 4, 6 or 8",
+        )
+    };
+    if ORDER == 4 {
+        return try_key_to_frequency_approx(key);
+    }
+    let (m, o) = try_key_to_params_top(key)?;
+    let m = m as f32;
+    let f = match ORDER {
+        3 => C::const_new(0.0, 4.0 / 11.0, consts::CHEBYSHEV_TOP_OCTAVE_3).eval_3(m),
+        6 => C::const_new(0.0, 4.0 / 11.0, consts::CHEBYSHEV_TOP_OCTAVE_6).eval_6(m),
+        8 => C::const_new(0.0, 4.0 / 11.0, consts::CHEBYSHEV_TOP_OCTAVE_HI).eval(m),
+        _ => unreachable!(),
+    };
+    let p = octave_scale(o);
+
+    Ok(f * p)
 }
 
 #[test]
-fn test_key_to_params_top() {
-    let tests: &[(u8, (u8, u8))] = &[
-        (116, (0, 0)),
-        (115, (11, 1)),
-        (69, (1, 4)),
-        (68, (0, 4)),
-        (67, (11, 5)),
-    ];
-    for &(key, vals) in tests {
-        assert_eq!(key_to_params_top(key), vals);
+fn test_key_to_frequency_approx_n() {
+    fn max_cents(f: fn(u8) -> f32) -> f64 {
+        let mut max_cents: f64 = 0.0;
+        for key in 0..=127 {
+            let cents = 1200.0 * f64::log2(f(key) as f64 / key_to_frequency_f64(key));
+            max_cents = max_cents.max(cents.abs());
+        }
+        max_cents
+    }
+
+    for key in 0..=127 {
+        assert_eq!(
+            key_to_frequency_approx_n::<4>(key),
+            key_to_frequency_approx_raw(key)
+        );
+        assert_eq!(
+            key_to_frequency_approx_n::<8>(key),
+            key_to_frequency_approx_hi(key)
+        );
     }
+    let e3 = max_cents(key_to_frequency_approx_n::<3>);
+    let e4 = max_cents(key_to_frequency_approx_n::<4>);
+    let e6 = max_cents(key_to_frequency_approx_n::<6>);
+    let e8 = max_cents(key_to_frequency_approx_n::<8>);
+    assert!(e3 < 3.0, "{}", e3);
+    assert!(e4 < 0.12, "{}", e4);
+    assert!(e6 < 0.0012, "{}", e6);
+    assert!(e8 < 0.00013, "{}", e8);
 }
 
-/// Computes the approximate frequency for a given midi key
-/// value using a formula involving a Chebyshev series. (See
-/// the source code for details.) The accuracy is better
-/// than 0.1¢
+/// Double-precision version of [key_to_frequency_approx],
+/// using a 10-term Chebyshev series. The relative error is
+/// better than $10^{-13}$ (about $2 \times 10^{-10}$¢).
 ///
 /// # Examples
 ///
 /// ```
-/// # use keytones::key_to_frequency_approx;
-/// assert_eq!(key_to_frequency_approx(69).round(), 440.0);
+/// # use keytones::key_to_frequency_approx_f64;
+/// assert_eq!(key_to_frequency_approx_f64(69).round(), 440.0);
 /// ```
 ///
 /// # Panics
 ///
 /// Panics if `key` is not in the range `0..=127`.
-pub fn key_to_frequency_approx(key: u8) -> f32 {
-    let (m, o) = key_to_params_top(key);
-    let approx = C::const_new(0.0, 4.0 / 11.0, consts::CHEBYSHEV_TOP_OCTAVE);
-    let f = approx.eval_4(m as f32);
-    let p = f32::powf(2.0, -(o as f32));
+pub fn key_to_frequency_approx_f64(key: u8) -> f64 {
+    or_panic(try_key_to_frequency_approx_f64(key))
+}
+
+/// Double-precision version of [try_key_to_frequency_approx].
+pub fn try_key_to_frequency_approx_f64(key: u8) -> Result<f64, KeyError> {
+    let (m, o) = try_key_to_params_top(key)?;
+    let f = eval_f64(&consts::CHEBYSHEV_TOP_OCTAVE_F64, m as f64);
+    let p = octave_scale_f64(o);
 
-    f * p
+    Ok(f * p)
 }
 
 #[cfg(test)]
 mod test {
-    fn matches(k: u8, f: fn(u8) -> f32, g: fn(u8) -> f32, prec: f32) -> bool {
+    use core::fmt::{Debug, Display};
+
+    use num_traits::Float;
+
+    fn matches<T: Float>(k: u8, f: fn(u8) -> T, g: fn(u8) -> T, prec: T) -> bool {
         let x = f(k);
         let y = g(k);
-        f32::abs(x - y) < prec * f32::min(x, y)
+        (x - y).abs() < prec * x.min(y)
     }
 
-    pub fn check(f: fn(u8) -> f32, g: fn(u8) -> f32, prec: f32) {
-        for k in 0..127 {
+    pub fn check<T: Float + Display>(f: fn(u8) -> T, g: fn(u8) -> T, prec: T) {
+        for k in 0..=127 {
             assert!(
                 matches(k, f, g, prec),
                 "{} {} {}",
@@ -129,59 +1140,603 @@ mod test {
             );
         }
     }
+
+    pub fn check_try<T: PartialEq + Debug>(
+        f: fn(u8) -> T,
+        try_f: fn(u8) -> Result<T, crate::KeyError>,
+    ) {
+        for k in 0..=127 {
+            assert_eq!(try_f(k), Ok(f(k)));
+        }
+        for k in [128, 200, 255] {
+            assert_eq!(try_f(k), Err(crate::KeyError::OutOfRange { key: k }));
+        }
+    }
+
+    pub fn check_checked(f: fn(u8) -> f32, checked: fn(u8) -> Option<f32>) {
+        for k in 0..=127 {
+            assert_eq!(checked(k), Some(f(k)));
+        }
+        for k in 128..=255 {
+            assert_eq!(checked(k), None);
+        }
+    }
 }
 
 #[test]
 fn test_key_to_frequency_approx() {
-    test::check(key_to_frequency, key_to_frequency_approx, 0.001);
+    test::check(key_to_frequency_raw, key_to_frequency_approx_raw, 0.001);
+}
+
+#[test]
+fn test_key_to_frequency_approx_f64() {
+    test::check(key_to_frequency_f64, key_to_frequency_approx_f64, 1.0e-13);
+}
+
+/// Approximates the frequency ratio $2^{x/12}$ of `x`
+/// semitones using a Chebyshev series over a single octave,
+/// scaled by a power of two. No `powf` is needed. The
+/// accuracy is better than 0.01¢ wherever the ratio is a
+/// normal `f32`. Ratios too large or too small for an `f32`
+/// go to infinity or zero, as with `powf`. Whole octaves,
+/// including 0, give exact powers of two.
+fn semitones_to_ratio_approx(x: f32) -> f32 {
+    let o = num_traits::Float::floor(x / 12.0);
+    let m = x - 12.0 * o;
+    // Split 2^o into two factors, each built directly from
+    // its exponent bits, so that an `o` past the exponent
+    // range of a single `f32` saturates rather than wrapping.
+    let o = o.clamp(-300.0, 300.0) as i32;
+    let pow2 = |e: i32| f32::from_bits(((e.clamp(-126, 127) + 127) as u32) << 23);
+    let (p, q) = (pow2(o / 2), pow2(o - o / 2));
+    if m == 0.0 || x.is_infinite() {
+        return p * q;
+    }
+    let approx = C::const_new(0.0, 4.0 / 12.0, consts::CHEBYSHEV_OCTAVE);
+    approx.eval_5(m) * p * q
 }
 
-fn key_to_params_bottom(key: u8) -> (u8, u8) {
-    assert!(key < 128);
-    let m = key % 12;
-    let o = key / 12;
-    (m, o)
+/// Computes the approximate frequency for a fractional midi
+/// key value using a Chebyshev series, without calling
+/// `powf`. The accuracy is better than 0.01¢.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_frequency_fractional_approx;
+/// assert_eq!(key_to_frequency_fractional_approx(69.0).round(), 440.0);
+/// assert_eq!(key_to_frequency_fractional_approx(68.5).round(), 427.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0.0..=127.0`.
+pub fn key_to_frequency_fractional_approx(key: f32) -> f32 {
+    assert!(
+        (0.0..=127.0).contains(&key),
+        "fractional key {} out of range 0.0..=127.0",
+        key,
+    );
+    440.0 * semitones_to_ratio_approx(key - 69.0)
 }
 
 #[test]
-fn test_key_to_params_bottom() {
-    let tests: &[(u8, (u8, u8))] = &[
-        (0, (0, 0)),
-        (1, (1, 0)),
-        (69, (9, 5)),
-        (68, (8, 5)),
-        (67, (7, 5)),
-    ];
-    for &(key, vals) in tests {
-        assert_eq!(key_to_params_bottom(key), vals);
+fn test_key_to_frequency_fractional_approx() {
+    for i in 0..=12700 {
+        let k = i as f32 / 100.0;
+        let f = key_to_frequency_fractional(k);
+        let g = key_to_frequency_fractional_approx(k);
+        // 0.01¢ is a ratio of about 5.8e-6.
+        assert!(f32::abs(f - g) < 5.8e-6 * f, "{} {} {}", k, f, g);
     }
 }
 
 /// Computes the approximate unit period for a given midi key
 /// value using a formula involving a Chebyshev series. (See
 /// the source code for details.) The accuracy is better
-/// than 0.1%.
+/// than 0.12¢ (about 0.007%); run `cargo run --example
+/// accuracy` for measured errors. Every A is exact to
+/// within rounding, so that the period of A4 (key 69) is
+/// `1.0 / 440.0`.
+///
+/// Octaves are exact: the result for key `k + 12` is
+/// always exactly half that for key `k`.
 ///
 /// # Examples
 ///
 /// ```
 /// # use keytones::key_to_period_approx;
-/// assert_eq!((1.0 / key_to_period_approx(69)).round(), 440.0);
+/// assert_eq!((1.0 / key_to_period_approx(69).0).round(), 440.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_period_approx(key: u8) -> Period {
+    Period(key_to_period_approx_raw(key))
+}
+
+/// Like [key_to_period_approx], but returns the period in
+/// seconds as a bare `f32`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period_approx_raw;
+/// assert_eq!((1.0 / key_to_period_approx_raw(69)).round(), 440.0);
 /// ```
 ///
 /// # Panics
 ///
 /// Panics if `key` is not in the range `0..=127`.
-pub fn key_to_period_approx(key: u8) -> f32 {
-    let (m, o) = key_to_params_bottom(key);
+pub fn key_to_period_approx_raw(key: u8) -> f32 {
+    or_panic(try_key_to_period_approx(key))
+}
+
+/// Like [key_to_period_approx], but returns an error rather
+/// than panicking if `key` is not in the range `0..=127`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{try_key_to_period_approx, KeyError};
+/// assert_eq!(try_key_to_period_approx(69).map(|p| (1.0 / p).round()), Ok(440.0));
+/// assert_eq!(try_key_to_period_approx(128), Err(KeyError::OutOfRange { key: 128 }));
+/// ```
+pub fn try_key_to_period_approx(key: u8) -> Result<f32, KeyError> {
+    let (m, o) = try_key_to_params_bottom(key)?;
     let approx = C::const_new(0.0, 4.0 / 11.0, consts::CHEBYSHEV_BOTTOM_OCTAVE);
-    let f = approx.eval_4(m as f32);
-    let p = f32::powf(2.0, -(o as f32));
+    // Key 9 is A-1, at 13.75 Hz. Pinning it pins every A.
+    let f = if m == 9 {
+        1.0 / 13.75
+    } else {
+        approx.eval_4(m as f32)
+    };
+    let p = octave_scale(o);
+
+    Ok(f * p)
+}
 
-    f * p
+/// Like [key_to_period_approx], but returns `None` rather
+/// than panicking if `key` is not in the range `0..=127`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period_approx_checked;
+/// assert_eq!(key_to_period_approx_checked(69).map(|p| (1.0 / p).round()), Some(440.0));
+/// assert_eq!(key_to_period_approx_checked(128), None);
+/// ```
+pub fn key_to_period_approx_checked(key: u8) -> Option<f32> {
+    try_key_to_period_approx(key).ok()
 }
 
 #[test]
 fn test_key_to_period_approx() {
-    test::check(key_to_period, key_to_period_approx, 0.001);
+    test::check(key_to_period_raw, key_to_period_approx_raw, 0.001);
+}
+
+#[test]
+fn test_approx_a_exact() {
+    assert_eq!(key_to_frequency_approx_raw(69), 440.0);
+    assert_eq!(key_to_period_approx_raw(69), 1.0 / 440.0);
+    for key in (9..=117).step_by(12) {
+        assert_eq!(key_to_frequency_approx_raw(key), key_to_frequency_raw(key));
+        let p = key_to_period_approx_raw(key);
+        let exact = 1.0 / key_to_frequency_raw(key);
+        assert!(p == exact || p.next_up() == exact || p.next_down() == exact);
+    }
+}
+
+#[test]
+fn test_approx_octaves_exact() {
+    for k in 0..=115 {
+        let f = key_to_frequency_approx_raw(k);
+        assert_eq!(
+            key_to_frequency_approx_raw(k + 12).to_bits(),
+            (2.0 * f).to_bits()
+        );
+        let f = key_to_frequency_approx_hi(k);
+        assert_eq!(
+            key_to_frequency_approx_hi(k + 12).to_bits(),
+            (2.0 * f).to_bits()
+        );
+        let p = key_to_period_approx_raw(k + 12);
+        assert_eq!(key_to_period_approx_raw(k).to_bits(), (2.0 * p).to_bits());
+        let p = key_to_period_approx_hi(k + 12);
+        assert_eq!(key_to_period_approx_hi(k).to_bits(), (2.0 * p).to_bits());
+    }
+}
+
+#[test]
+fn test_monotonic() {
+    let frequencies: [fn(u8) -> f32; 8] = [
+        key_to_frequency_raw,
+        key_to_frequency_table,
+        key_to_frequency_approx_raw,
+        key_to_frequency_approx_hi,
+        key_to_frequency_approx_n::<3>,
+        key_to_frequency_approx_n::<6>,
+        |k| key_to_frequency_f64(k) as f32,
+        |k| key_to_frequency_approx_f64(k) as f32,
+    ];
+    let periods: [fn(u8) -> f32; 6] = [
+        key_to_period_raw,
+        key_to_period_table,
+        key_to_period_approx_raw,
+        key_to_period_approx_hi,
+        |k| key_to_period_f64(k) as f32,
+        |k| key_to_period_approx_f64(k) as f32,
+    ];
+    // This includes the octave seams at 11/12 and 115/116.
+    for k in 0..=126 {
+        for (i, f) in frequencies.iter().enumerate() {
+            assert!(f(k) < f(k + 1), "{} {} {} {}", i, k, f(k), f(k + 1));
+        }
+        for (i, p) in periods.iter().enumerate() {
+            assert!(p(k) > p(k + 1), "{} {} {} {}", i, k, p(k), p(k + 1));
+        }
+    }
+}
+
+#[test]
+fn test_approx_octave_boundaries() {
+    // Keys at and around the ends of the reference octaves.
+    for key in [0, 11, 12, 116, 119, 120, 127] {
+        let f = key_to_frequency_approx_raw(key) as f64;
+        let cents = 1200.0 * f64::log2(f / key_to_frequency_f64(key));
+        assert!(cents.abs() < 0.12, "{} {}", key, cents);
+        let p = key_to_period_approx_raw(key) as f64;
+        let cents = 1200.0 * f64::log2(key_to_period_f64(key) / p);
+        assert!(cents.abs() < 0.12, "{} {}", key, cents);
+    }
+}
+
+/// Computes the approximate unit period for a given midi
+/// key value as [key_to_period_approx] does, but using a
+/// higher-order Chebyshev series. The worst-case error
+/// over all keys is about 0.00006¢, at the cost of a larger
+/// coefficient table and a few more multiplies.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period_approx_hi;
+/// assert_eq!((1.0 / key_to_period_approx_hi(69)).round(), 440.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_period_approx_hi(key: u8) -> f32 {
+    or_panic(try_key_to_period_approx_hi(key))
+}
+
+/// Like [key_to_period_approx_hi], but returns an error
+/// rather than panicking if `key` is not in the range
+/// `0..=127`.
+pub fn try_key_to_period_approx_hi(key: u8) -> Result<f32, KeyError> {
+    let (m, o) = try_key_to_params_bottom(key)?;
+    let approx = C::const_new(0.0, 4.0 / 11.0, consts::CHEBYSHEV_BOTTOM_OCTAVE_HI);
+    let f = approx.eval(m as f32);
+    let p = octave_scale(o);
+
+    Ok(f * p)
+}
+
+#[test]
+fn test_key_to_period_approx_hi() {
+    let mut max_cents: f64 = 0.0;
+    for key in 0..=127 {
+        let p = key_to_period_approx_hi(key) as f64;
+        let cents = 1200.0 * f64::log2(key_to_period_f64(key) / p);
+        max_cents = max_cents.max(cents.abs());
+    }
+    assert!(max_cents < 0.001, "{}", max_cents);
+}
+
+/// Double-precision version of [key_to_period_approx],
+/// using a 10-term Chebyshev series. The relative error is
+/// better than $10^{-13}$ (about $2 \times 10^{-10}$¢).
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period_approx_f64;
+/// assert_eq!((1.0 / key_to_period_approx_f64(69)).round(), 440.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_period_approx_f64(key: u8) -> f64 {
+    or_panic(try_key_to_period_approx_f64(key))
+}
+
+/// Double-precision version of [try_key_to_period_approx].
+pub fn try_key_to_period_approx_f64(key: u8) -> Result<f64, KeyError> {
+    let (m, o) = try_key_to_params_bottom(key)?;
+    let f = eval_f64(&consts::CHEBYSHEV_BOTTOM_OCTAVE_F64, m as f64);
+    let p = octave_scale_f64(o);
+
+    Ok(f * p)
+}
+
+#[test]
+fn test_key_to_period_approx_f64() {
+    test::check(key_to_period_f64, key_to_period_approx_f64, 1.0e-13);
+}
+
+/// Computes the continuous "fractional" midi key for a
+/// given frequency $f$ in Hz, using the formula
+///    $$69 + 12 \log_2 \frac{f}{440}$$
+/// The result is not rounded or clamped, so frequencies
+/// outside the range of midi keys give values outside
+/// $[0..127]$. A frequency that is not a positive number
+/// gives a NaN or infinite result.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::frequency_to_key_fractional;
+/// assert_eq!(frequency_to_key_fractional(440.0), 69.0);
+/// assert_eq!(frequency_to_key_fractional(880.0), 81.0);
+/// assert!(frequency_to_key_fractional(2.0) < 0.0);
+/// ```
+pub fn frequency_to_key_fractional(freq: f32) -> f32 {
+    69.0 + 12.0 * f32::log2(freq / 440.0)
+}
+
+/// Like [frequency_to_key_fractional], but returns `None`
+/// rather than a non-finite result if `freq` is not a finite
+/// positive number.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::frequency_to_key_fractional_checked;
+/// assert_eq!(frequency_to_key_fractional_checked(440.0), Some(69.0));
+/// assert_eq!(frequency_to_key_fractional_checked(0.0), None);
+/// ```
+pub fn frequency_to_key_fractional_checked(freq: f32) -> Option<f32> {
+    if !(freq > 0.0 && freq.is_finite()) {
+        return None;
+    }
+    Some(frequency_to_key_fractional(freq))
+}
+
+/// Computes the approximate fractional midi key for a
+/// given frequency in Hz, as [frequency_to_key_fractional]
+/// but without calling `log2`: the octave is taken from
+/// the floating-point exponent and the remainder is found
+/// with a Chebyshev series. The accuracy is better than
+/// 0.05¢.
+///
+/// The result is meaningless if `freq` is not a finite
+/// positive normal number.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::frequency_to_key_fractional_approx;
+/// assert_eq!(frequency_to_key_fractional_approx(440.0).round(), 69.0);
+/// ```
+pub fn frequency_to_key_fractional_approx(freq: f32) -> f32 {
+    let bits = freq.to_bits();
+    let e = ((bits >> 23) & 0xff) as i32 - 127;
+    // Mantissa with a zero exponent: a value in [1..2).
+    let m = f32::from_bits((bits & 0x7f_ffff) | (127 << 23));
+    let approx = C::const_new(1.0, 4.0, consts::CHEBYSHEV_LOG_OCTAVE);
+    12.0 * e as f32 + approx.eval_6(m)
+}
+
+#[test]
+fn test_frequency_to_key_fractional_approx() {
+    let mut freq = 1.0f32;
+    while freq < 20000.0 {
+        let k = frequency_to_key_fractional(freq);
+        let a = frequency_to_key_fractional_approx(freq);
+        assert!(f32::abs(k - a) < 0.0005, "{} {} {}", freq, k, a);
+        freq *= 1.001;
+    }
+}
+
+#[test]
+fn test_frequency_to_key_fractional() {
+    for k in 0..=127 {
+        let kf = frequency_to_key_fractional(key_to_frequency_raw(k));
+        assert!(f32::abs(kf - k as f32) < 1.0e-4, "{} {}", k, kf);
+        assert_eq!(
+            frequency_to_key_fractional_checked(key_to_frequency_raw(k)),
+            Some(kf)
+        );
+    }
+
+    assert_eq!(frequency_to_key_fractional(27.5 / 4.0), -3.0);
+    assert_eq!(frequency_to_key_fractional(440.0 * 8.0), 105.0);
+    assert_eq!(frequency_to_key_fractional(440.0 * 64.0), 141.0);
+
+    for freq in [0.0, -1.0, f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+        assert_eq!(frequency_to_key_fractional_checked(freq), None, "{}", freq);
+    }
+}
+
+/// Finds the midi key whose frequency is nearest to the
+/// given frequency in Hz. Distance is measured in pitch
+/// (log frequency), so a frequency exactly halfway between
+/// two keys rounds up to the higher key.
+///
+/// Returns `None` if the frequency is more than half a
+/// semitone below key 0 or at least half a semitone above
+/// key 127, or is not a positive number.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::frequency_to_key;
+/// assert_eq!(frequency_to_key(440.0), Some(69));
+/// assert_eq!(frequency_to_key(435.0), Some(69));
+/// assert_eq!(frequency_to_key(0.0), None);
+/// ```
+pub fn frequency_to_key(freq: f32) -> Option<u8> {
+    frequency_to_key_cents(freq).map(|(key, _)| key)
+}
+
+/// Like [frequency_to_key], but clamps the result to the
+/// range `0..=127` rather than failing: frequencies above
+/// key 127 give 127, and frequencies below key 0 give 0. A
+/// frequency that is not a positive number gives 0.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::frequency_to_key_saturating;
+/// assert_eq!(frequency_to_key_saturating(440.0), 69);
+/// assert_eq!(frequency_to_key_saturating(20000.0), 127);
+/// assert_eq!(frequency_to_key_saturating(1.0), 0);
+/// ```
+pub fn frequency_to_key_saturating(freq: f32) -> u8 {
+    let k = frequency_to_key_fractional(freq);
+    // Float-to-int casts saturate, and take NaN to 0.
+    ((k + 0.5) as u8).min(127)
+}
+
+/// Split a fractional key into the nearest key and a signed
+/// offset in cents in the range `-50.0..50.0`.
+fn key_real_to_key_cents(k: f32) -> Option<(u8, f32)> {
+    if !(-0.5..127.5).contains(&k) {
+        return None;
+    }
+    // k + 0.5 is non-negative here, so truncation is floor.
+    let key = (k + 0.5) as u8;
+    Some((key, 100.0 * (k - key as f32)))
+}
+
+#[test]
+fn test_frequency_to_key() {
+    for k in 0..=127 {
+        assert_eq!(frequency_to_key(key_to_frequency_raw(k)), Some(k));
+    }
+
+    let tests: &[(f32, Option<u8>)] = &[
+        (435.0, Some(69)),
+        (446.0, Some(69)),
+        (454.0, Some(70)),
+        (427.0, Some(68)),
+        (8.0, Some(0)),
+        (7.5, None),
+        (12900.0, Some(127)),
+        (13000.0, None),
+        (0.0, None),
+        (-440.0, None),
+        (f32::NAN, None),
+        (f32::INFINITY, None),
+    ];
+    for &(freq, key) in tests {
+        assert_eq!(frequency_to_key(freq), key, "{}", freq);
+    }
+}
+
+#[test]
+fn test_checked() {
+    test::check_checked(key_to_frequency_raw, key_to_frequency_checked);
+    test::check_checked(key_to_period_raw, key_to_period_checked);
+    test::check_checked(key_to_frequency_approx_raw, key_to_frequency_approx_checked);
+    test::check_checked(key_to_period_approx_raw, key_to_period_approx_checked);
+}
+
+/// Finds the midi key whose frequency is nearest to the
+/// given frequency in Hz, together with the signed
+/// deviation of the frequency from that key in cents. The
+/// deviation is in the range `-50.0..50.0`: a frequency
+/// exactly halfway between two keys is reported as 50¢
+/// flat of the higher key, consistent with
+/// [frequency_to_key].
+///
+/// Returns `None` under the same conditions as
+/// [frequency_to_key].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::frequency_to_key_cents;
+/// let (key, cents) = frequency_to_key_cents(446.0).unwrap();
+/// assert_eq!(key, 69);
+/// assert_eq!(cents.round(), 23.0);
+/// ```
+pub fn frequency_to_key_cents(freq: f32) -> Option<(u8, f32)> {
+    key_real_to_key_cents(frequency_to_key_fractional(freq))
+}
+
+#[test]
+fn test_frequency_to_key_cents() {
+    for k in 0..=127 {
+        let (key, cents) = frequency_to_key_cents(key_to_frequency_raw(k)).unwrap();
+        assert_eq!(key, k);
+        assert!(f32::abs(cents) < 0.01, "{} {}", k, cents);
+    }
+
+    let tests: &[(f32, Option<(u8, f32)>)] = &[
+        (435.0, Some((69, -19.78))),
+        (446.0, Some((69, 23.45))),
+        (454.0, Some((70, -45.77))),
+        (7.5, None),
+        (13000.0, None),
+        (0.0, None),
+    ];
+    for &(freq, expected) in tests {
+        let result = frequency_to_key_cents(freq);
+        match (result, expected) {
+            (Some((k, c)), Some((ek, ec))) => {
+                assert_eq!(k, ek, "{}", freq);
+                assert!(f32::abs(c - ec) < 0.01, "{} {} {}", freq, c, ec);
+            }
+            _ => assert_eq!(result, expected, "{}", freq),
+        }
+    }
+
+    assert_eq!(key_real_to_key_cents(69.5), Some((70, -50.0)));
+    assert_eq!(key_real_to_key_cents(-0.5), Some((0, -50.0)));
+    assert_eq!(key_real_to_key_cents(127.5), None);
+    assert_eq!(key_real_to_key_cents(69.25), Some((69, 25.0)));
+}
+
+#[test]
+fn test_try() {
+    test::check_try(key_to_frequency_raw, try_key_to_frequency);
+    test::check_try(key_to_period_raw, try_key_to_period);
+    test::check_try(key_to_frequency_approx_raw, try_key_to_frequency_approx);
+    test::check_try(key_to_period_approx_raw, try_key_to_period_approx);
+    test::check_try(key_to_frequency_f64, try_key_to_frequency_f64);
+    test::check_try(key_to_period_f64, try_key_to_period_f64);
+    test::check_try(key_to_frequency_approx_f64, try_key_to_frequency_approx_f64);
+    test::check_try(key_to_period_approx_f64, try_key_to_period_approx_f64);
+}
+
+#[test]
+#[should_panic(expected = "key 128 out of range 0..=127")]
+fn test_key_error_panic() {
+    key_to_frequency_raw(128);
+}
+
+#[test]
+fn test_frequency_to_key_saturating() {
+    for k in 0..=127 {
+        assert_eq!(frequency_to_key_saturating(key_to_frequency_raw(k)), k);
+    }
+
+    let tests: &[(f32, u8)] = &[
+        (435.0, 69),
+        (454.0, 70),
+        (7.5, 0),
+        (13000.0, 127),
+        (1.0e30, 127),
+        (0.0, 0),
+        (-440.0, 0),
+        (f32::NAN, 0),
+        (f32::INFINITY, 127),
+    ];
+    for &(freq, key) in tests {
+        assert_eq!(frequency_to_key_saturating(freq), key, "{}", freq);
+    }
 }
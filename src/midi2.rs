@@ -0,0 +1,177 @@
+//! MIDI 2.0 pitch representations.
+
+use crate::{
+    check_key, fractional_key_to_frequency, frequency_to_key_fractional,
+    frequency_to_key_fractional_approx, or_panic, semitones_to_ratio_approx,
+};
+
+#[cfg(all(not(feature = "std"), not(test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+/// Round a fractional key to the nearest Pitch 7.9 value,
+/// clamping to the representable range. NaN gives 0.
+fn key_to_pitch_7_9(key: f32) -> u16 {
+    // Float-to-int casts saturate, and take NaN to 0.
+    (key * 512.0 + 0.5) as u16
+}
+
+/// Computes the frequency of a MIDI 2.0 "Pitch 7.9" value:
+/// a 16-bit fixed-point midi key with 7 integer bits and 9
+/// fractional bits, so that the resolution is 1/512
+/// semitone. The largest value, `0xFFFF`, is just short of
+/// key 128.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::pitch_7_9_to_frequency;
+/// assert_eq!(pitch_7_9_to_frequency(69 << 9), 440.0);
+/// assert_eq!(pitch_7_9_to_frequency((60 << 9) + 256).round(), 269.0);
+/// ```
+pub fn pitch_7_9_to_frequency(raw: u16) -> f32 {
+    fractional_key_to_frequency(raw as f32 / 512.0)
+}
+
+/// Computes the approximate frequency of a MIDI 2.0 Pitch
+/// 7.9 value, as [pitch_7_9_to_frequency] but without
+/// calling `powf`. The accuracy is better than 0.01¢.
+pub fn pitch_7_9_to_frequency_approx(raw: u16) -> f32 {
+    440.0 * semitones_to_ratio_approx(raw as f32 / 512.0 - 69.0)
+}
+
+/// Finds the MIDI 2.0 Pitch 7.9 value nearest to the given
+/// frequency in Hz. Frequencies below key 0 give 0, and
+/// frequencies at or above key 128 give `0xFFFF`. A
+/// frequency that is not a positive number gives 0.
+///
+/// Every Pitch 7.9 value round-trips exactly through
+/// [pitch_7_9_to_frequency] and this function.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::frequency_to_pitch_7_9;
+/// assert_eq!(frequency_to_pitch_7_9(440.0), 69 << 9);
+/// ```
+pub fn frequency_to_pitch_7_9(freq: f32) -> u16 {
+    key_to_pitch_7_9(frequency_to_key_fractional(freq))
+}
+
+/// Finds the approximate MIDI 2.0 Pitch 7.9 value nearest to
+/// the given frequency in Hz, as [frequency_to_pitch_7_9]
+/// but without calling `log2`. The result may differ from
+/// the exact one by one unit.
+pub fn frequency_to_pitch_7_9_approx(freq: f32) -> u16 {
+    if freq.is_nan() || freq < f32::MIN_POSITIVE {
+        return 0;
+    }
+    key_to_pitch_7_9(frequency_to_key_fractional_approx(freq))
+}
+
+/// Builds a MIDI 2.0 Pitch 7.9 value from a midi key and a
+/// signed offset in cents, rounding to the nearest 1/512
+/// semitone and clamping to the representable range.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_cents_to_pitch_7_9;
+/// assert_eq!(key_cents_to_pitch_7_9(60, 50.0), (60 << 9) + 256);
+/// assert_eq!(key_cents_to_pitch_7_9(0, -10.0), 0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_cents_to_pitch_7_9(key: u8, cents: f32) -> u16 {
+    let key = or_panic(check_key(key));
+    key_to_pitch_7_9(key as f32 + cents / 100.0)
+}
+
+/// Computes the frequency of a MIDI 2.0 32-bit note number:
+/// a fixed-point midi key with 7 integer bits and 25
+/// fractional bits, so that the resolution is 1/2<sup>25</sup>
+/// semitone. The integer key is `note >> 25`, and the
+/// fractional offset is the low 25 bits. Every `u32` value
+/// is a valid note number.
+///
+/// The computation is done in double precision, so that
+/// the sub-cent resolution survives until the result is
+/// rounded to `f32`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::midi2_note_to_frequency;
+/// assert_eq!(midi2_note_to_frequency(69 << 25), 440.0);
+/// assert_eq!(midi2_note_to_frequency((81 << 25) + (1 << 24)).round(), 906.0);
+/// ```
+pub fn midi2_note_to_frequency(note: u32) -> f32 {
+    midi2_note_to_frequency_f64(note) as f32
+}
+
+/// Double-precision version of [midi2_note_to_frequency].
+pub fn midi2_note_to_frequency_f64(note: u32) -> f64 {
+    let key = (note >> 25) as f64 + (note & 0x01FF_FFFF) as f64 / (1 << 25) as f64;
+    440.0 * f64::powf(2.0, (key - 69.0) / 12.0)
+}
+
+#[test]
+fn test_midi2_note_to_frequency() {
+    assert_eq!(midi2_note_to_frequency(69 << 25), 440.0);
+    assert_eq!(midi2_note_to_frequency_f64(69 << 25), 440.0);
+    assert_eq!(midi2_note_to_frequency(57 << 25), 220.0);
+    for key in 0..=127u32 {
+        assert_eq!(
+            midi2_note_to_frequency(key << 25),
+            crate::key_to_frequency_f64(key as u8) as f32,
+        );
+    }
+
+    // A quarter tone above A4 is 50 cents sharp.
+    let f = midi2_note_to_frequency_f64((69 << 25) + (1 << 24));
+    let cents = 1200.0 * f64::log2(f / 440.0);
+    assert!((cents - 50.0).abs() < 1.0e-9, "{}", cents);
+
+    // One unit is 100/2^25 cents.
+    let f = midi2_note_to_frequency_f64((69 << 25) + 1);
+    let cents = 1200.0 * f64::log2(f / 440.0);
+    let unit = 100.0 / (1u32 << 25) as f64;
+    assert!((cents - unit).abs() < 1.0e-3 * unit, "{}", cents);
+
+    let f = midi2_note_to_frequency_f64(u32::MAX);
+    let top = crate::key_to_frequency_f64(127) * f64::powf(2.0, 1.0 / 12.0);
+    assert!(f < top && f > 0.999999 * top, "{}", f);
+}
+
+#[test]
+fn test_pitch_7_9() {
+    use crate::key_to_frequency_raw;
+
+    for k in 0..=127 {
+        assert_eq!(
+            pitch_7_9_to_frequency((k as u16) << 9),
+            key_to_frequency_raw(k)
+        );
+    }
+    for raw in 0..=u16::MAX {
+        let f = pitch_7_9_to_frequency(raw);
+        assert_eq!(frequency_to_pitch_7_9(f), raw);
+        let a = pitch_7_9_to_frequency_approx(raw);
+        assert!(f32::abs(f - a) < 5.8e-6 * f, "{} {} {}", raw, f, a);
+        let r = frequency_to_pitch_7_9_approx(f);
+        assert!(r.abs_diff(raw) <= 1, "{} {}", raw, r);
+    }
+
+    assert_eq!(frequency_to_pitch_7_9(1.0), 0);
+    assert_eq!(frequency_to_pitch_7_9(20000.0), 0xFFFF);
+    assert_eq!(frequency_to_pitch_7_9(0.0), 0);
+    assert_eq!(frequency_to_pitch_7_9(f32::NAN), 0);
+    assert_eq!(frequency_to_pitch_7_9_approx(0.0), 0);
+    assert_eq!(frequency_to_pitch_7_9_approx(20000.0), 0xFFFF);
+
+    assert_eq!(key_cents_to_pitch_7_9(69, 0.0), 69 << 9);
+    assert_eq!(key_cents_to_pitch_7_9(69, -100.0), 68 << 9);
+    assert_eq!(key_cents_to_pitch_7_9(127, 100.0), 0xFFFF);
+}
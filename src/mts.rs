@@ -0,0 +1,161 @@
+//! MIDI Tuning Standard frequency data.
+
+use crate::{check_key, or_panic};
+
+#[cfg(all(not(feature = "std"), not(test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+/// MIDI Tuning Standard frequency data meaning "no change":
+/// a key tuned to this value keeps its current tuning.
+pub const MTS_NO_CHANGE: [u8; 3] = [0x7F, 0x7F, 0x7F];
+
+/// Number of MTS tuning units in a semitone.
+const UNITS_PER_SEMITONE: u32 = 1 << 14;
+
+/// Finds the MIDI Tuning Standard frequency data nearest to
+/// the given frequency in Hz. The three data bytes are a
+/// midi key, then the high and low seven bits of a 14-bit
+/// fraction of a semitone above it, so that the resolution
+/// is 1/16384 semitone, about 0.0061¢. The frequency is
+/// rounded to the nearest unit.
+///
+/// Gives `None` if the rounded frequency is below key 0, or
+/// is at or above key 127 plus 16383/16384 semitone, whose
+/// data is [MTS_NO_CHANGE]. Also gives `None` if the
+/// frequency is not a finite positive number.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::frequency_to_mts_bytes;
+/// assert_eq!(frequency_to_mts_bytes(440.0), Some([69, 0, 0]));
+/// // A quarter tone above A4.
+/// assert_eq!(frequency_to_mts_bytes(452.893), Some([69, 0x40, 0]));
+/// assert_eq!(frequency_to_mts_bytes(8.0), None);
+/// ```
+pub fn frequency_to_mts_bytes(freq: f32) -> Option<[u8; 3]> {
+    if freq.is_nan() || freq <= 0.0 || freq.is_infinite() {
+        return None;
+    }
+    let key = 69.0 + 12.0 * f64::log2(freq as f64 / 440.0);
+    let units = f64::round(key * UNITS_PER_SEMITONE as f64);
+    let top = (128 * UNITS_PER_SEMITONE - 1) as f64;
+    if !(0.0..top).contains(&units) {
+        return None;
+    }
+    let units = units as u32;
+    Some([
+        (units >> 14) as u8,
+        ((units >> 7) & 0x7F) as u8,
+        (units & 0x7F) as u8,
+    ])
+}
+
+/// Computes the frequency in Hz of MIDI Tuning Standard
+/// frequency data, as described for
+/// [frequency_to_mts_bytes]. The computation is done in
+/// double precision. [MTS_NO_CHANGE] carries no frequency,
+/// and gives NaN.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::mts_bytes_to_frequency;
+/// assert_eq!(mts_bytes_to_frequency([69, 0, 0]), 440.0);
+/// assert_eq!(mts_bytes_to_frequency([69, 0x40, 0]).round(), 453.0);
+/// assert!(mts_bytes_to_frequency([0x7F, 0x7F, 0x7F]).is_nan());
+/// ```
+///
+/// # Panics
+///
+/// Panics if the key byte is not in the range `0..=127`,
+/// or if either fraction byte is not in the range
+/// `0..=0x7F`.
+pub fn mts_bytes_to_frequency(bytes: [u8; 3]) -> f32 {
+    if bytes == MTS_NO_CHANGE {
+        return f32::NAN;
+    }
+    let key = or_panic(check_key(bytes[0]));
+    for b in [bytes[1], bytes[2]] {
+        assert!(b <= 0x7F, "MTS data byte {:#04x} out of range 0..=0x7f", b);
+    }
+    let fraction = ((bytes[1] as u32) << 7 | bytes[2] as u32) as f64;
+    let key = key as f64 + fraction / UNITS_PER_SEMITONE as f64;
+    (440.0 * f64::powf(2.0, (key - 69.0) / 12.0)) as f32
+}
+
+#[test]
+fn test_mts_bytes() {
+    use crate::{key_to_frequency_f64, key_to_frequency_raw};
+
+    for k in 0..=127 {
+        assert_eq!(
+            frequency_to_mts_bytes(key_to_frequency_raw(k)),
+            Some([k, 0, 0])
+        );
+        assert_eq!(
+            mts_bytes_to_frequency([k, 0, 0]),
+            key_to_frequency_f64(k) as f32
+        );
+    }
+
+    // Every value but "no change" round-trips.
+    let unit = 100.0 / UNITS_PER_SEMITONE as f64;
+    for units in 0..128 * UNITS_PER_SEMITONE - 1 {
+        let bytes = [
+            (units >> 14) as u8,
+            ((units >> 7) & 0x7F) as u8,
+            (units & 0x7F) as u8,
+        ];
+        let f = mts_bytes_to_frequency(bytes);
+        assert_eq!(frequency_to_mts_bytes(f), Some(bytes), "{:?}", bytes);
+    }
+
+    // Any frequency in range is within half a unit of its
+    // encoding, allowing for rounding the result to `f32`.
+    let bottom = key_to_frequency_f64(0);
+    for i in 0..100_000 {
+        let key = 127.99 * i as f64 / 100_000.0;
+        let f = (bottom * f64::powf(2.0, key / 12.0)) as f32;
+        let bytes = frequency_to_mts_bytes(f).unwrap();
+        let g = mts_bytes_to_frequency(bytes);
+        let cents = 1200.0 * f64::log2(g as f64 / f as f64);
+        assert!(f64::abs(cents) <= 0.5 * unit + 2.0e-4, "{} {}", f, cents);
+    }
+
+    // The ends of the range.
+    let at = |units: f64| (bottom * f64::powf(2.0, units / (12.0 * 16384.0))) as f32;
+    let top = (128 * UNITS_PER_SEMITONE - 1) as f64;
+    assert_eq!(frequency_to_mts_bytes(at(0.0)), Some([0, 0, 0]));
+    assert_eq!(frequency_to_mts_bytes(at(-0.4)), Some([0, 0, 0]));
+    assert_eq!(frequency_to_mts_bytes(at(-0.6)), None);
+    assert_eq!(frequency_to_mts_bytes(at(-1.0)), None);
+    assert_eq!(
+        frequency_to_mts_bytes(at(top - 1.0)),
+        Some([0x7F, 0x7F, 0x7E])
+    );
+    assert_eq!(
+        frequency_to_mts_bytes(at(top - 0.6)),
+        Some([0x7F, 0x7F, 0x7E])
+    );
+    assert_eq!(frequency_to_mts_bytes(at(top)), None);
+    assert_eq!(frequency_to_mts_bytes(at(top + 1.0)), None);
+
+    for f in [0.0, -440.0, f32::NAN, f32::INFINITY, f32::MIN_POSITIVE] {
+        assert_eq!(frequency_to_mts_bytes(f), None);
+    }
+    assert!(mts_bytes_to_frequency(MTS_NO_CHANGE).is_nan());
+}
+
+#[test]
+#[should_panic(expected = "key 128 out of range 0..=127")]
+fn test_mts_bytes_bad_key() {
+    mts_bytes_to_frequency([128, 0, 0]);
+}
+
+#[test]
+#[should_panic(expected = "MTS data byte 0x80 out of range 0..=0x7f")]
+fn test_mts_bytes_bad_fraction() {
+    mts_bytes_to_frequency([60, 0, 0x80]);
+}
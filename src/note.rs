@@ -0,0 +1,1214 @@
+//! Note names and pitch classes.
+
+#[cfg(feature = "std")]
+use std::string::String;
+
+use crate::{KeyError, check_key, consts, or_panic, try_key_to_params_bottom};
+
+/// Returns the name of the given midi key in scientific
+/// pitch notation, with middle C (key 60) as "C4". Black
+/// keys are spelled with sharps. Key 0 is "C-1" and key 127
+/// is "G9".
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_note_name;
+/// assert_eq!(key_to_note_name(69), "A4");
+/// assert_eq!(key_to_note_name(49), "C#3");
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_note_name(key: u8) -> &'static str {
+    consts::NOTE_NAMES_SHARP[or_panic(check_key(key)) as usize]
+}
+
+/// Returns the name of the given midi key as
+/// [key_to_note_name] does, but with black keys spelled
+/// with flats.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_note_name_flat;
+/// assert_eq!(key_to_note_name_flat(69), "A4");
+/// assert_eq!(key_to_note_name_flat(49), "Db3");
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_note_name_flat(key: u8) -> &'static str {
+    consts::NOTE_NAMES_FLAT[or_panic(check_key(key)) as usize]
+}
+
+/// Accidental used for black keys by
+/// [key_to_note_name_with_preference].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccidentalPreference {
+    /// Spell black keys with sharps, as [key_to_note_name]
+    /// does.
+    #[default]
+    Sharp,
+    /// Spell black keys with flats, as
+    /// [key_to_note_name_flat] does.
+    Flat,
+}
+
+impl From<AccidentalPreference> for Spelling {
+    fn from(pref: AccidentalPreference) -> Self {
+        match pref {
+            AccidentalPreference::Sharp => Spelling::Sharps,
+            AccidentalPreference::Flat => Spelling::Flats,
+        }
+    }
+}
+
+/// Returns the name of the given midi key as
+/// [key_to_note_name] does, with black keys spelled with
+/// the given accidental.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{key_to_note_name_with_preference, AccidentalPreference};
+/// let name = |key, pref| key_to_note_name_with_preference(key, pref);
+/// assert_eq!(name(70, AccidentalPreference::Sharp), "A#4");
+/// assert_eq!(name(70, AccidentalPreference::Flat), "Bb4");
+/// assert_eq!(name(69, AccidentalPreference::Flat), "A4");
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_note_name_with_preference(key: u8, pref: AccidentalPreference) -> &'static str {
+    match pref {
+        AccidentalPreference::Sharp => key_to_note_name(key),
+        AccidentalPreference::Flat => key_to_note_name_flat(key),
+    }
+}
+
+/// Returns the pitch class of the given midi key: its
+/// position within its octave, counting up from C.
+///
+/// | class | note    | class | note    |
+/// |-------|---------|-------|---------|
+/// | 0     | C       | 6     | F♯ / G♭ |
+/// | 1     | C♯ / D♭ | 7     | G       |
+/// | 2     | D       | 8     | G♯ / A♭ |
+/// | 3     | D♯ / E♭ | 9     | A       |
+/// | 4     | E       | 10    | A♯ / B♭ |
+/// | 5     | F       | 11    | B       |
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::pitch_class;
+/// assert_eq!(pitch_class(60), 0);
+/// assert_eq!(pitch_class(69), 9);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn pitch_class(key: u8) -> u8 {
+    or_panic(check_key(key)) % 12
+}
+
+/// Returns the key in the octave from middle C (key 60) to
+/// B4 (key 71) with the same [pitch class](pitch_class) as
+/// the given midi key.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::octave_fold;
+/// assert_eq!(octave_fold(21), 69);
+/// assert_eq!(octave_fold(127), 67);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn octave_fold(key: u8) -> u8 {
+    60 + pitch_class(key)
+}
+
+#[test]
+fn test_octave_fold() {
+    for key in 0..=127 {
+        let folded = octave_fold(key);
+        assert!((60..=71).contains(&folded), "{} {}", key, folded);
+        assert_eq!(pitch_class(folded), pitch_class(key));
+        assert_eq!(&key_to_note_name(folded)[..1], &key_to_note_name(key)[..1]);
+    }
+}
+
+/// A pitch class: a note name without an octave, spelled
+/// with sharps. Converting to `u8` gives the number used by
+/// [pitch_class], from 0 for C to 11 for B.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum PitchClass {
+    /// C: pitch class 0.
+    C = 0,
+    /// C♯, also spelled D♭: pitch class 1.
+    Cs = 1,
+    /// D: pitch class 2.
+    D = 2,
+    /// D♯, also spelled E♭: pitch class 3.
+    Ds = 3,
+    /// E: pitch class 4.
+    E = 4,
+    /// F: pitch class 5.
+    F = 5,
+    /// F♯, also spelled G♭: pitch class 6.
+    Fs = 6,
+    /// G: pitch class 7.
+    G = 7,
+    /// G♯, also spelled A♭: pitch class 8.
+    Gs = 8,
+    /// A: pitch class 9.
+    A = 9,
+    /// A♯, also spelled B♭: pitch class 10.
+    As = 10,
+    /// B: pitch class 11.
+    B = 11,
+}
+
+impl PitchClass {
+    /// All twelve pitch classes in order from C.
+    pub const ALL: [PitchClass; 12] = {
+        use PitchClass::*;
+        [C, Cs, D, Ds, E, F, Fs, G, Gs, A, As, B]
+    };
+}
+
+impl From<PitchClass> for u8 {
+    fn from(pitch_class: PitchClass) -> u8 {
+        pitch_class as u8
+    }
+}
+
+/// Returns the pitch class and octave of the given midi key
+/// in scientific pitch notation, where middle C (key 60) is
+/// C in octave 4 and key 0 is C in octave -1.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{key_to_pitch_class_octave, PitchClass};
+/// assert_eq!(key_to_pitch_class_octave(60), (PitchClass::C, 4));
+/// assert_eq!(key_to_pitch_class_octave(1), (PitchClass::Cs, -1));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_pitch_class_octave(key: u8) -> (PitchClass, i8) {
+    let (m, o) = or_panic(try_key_to_params_bottom(key));
+    (PitchClass::ALL[m as usize], o as i8 - 1)
+}
+
+/// Returns the midi key with the given pitch class in the
+/// given octave, as numbered by
+/// [key_to_pitch_class_octave], or `None` if there is no
+/// such key in the range `0..=127`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{pitch_class_octave_to_key, PitchClass};
+/// assert_eq!(pitch_class_octave_to_key(PitchClass::A, 4), Some(69));
+/// assert_eq!(pitch_class_octave_to_key(PitchClass::Gs, 9), None);
+/// ```
+pub fn pitch_class_octave_to_key(pitch_class: PitchClass, octave: i8) -> Option<u8> {
+    let key = 12 * (octave as i32 + 1) + pitch_class as i32;
+    u8::try_from(key).ok().filter(|&k| k <= 127)
+}
+
+#[test]
+fn test_pitch_class_octave() {
+    for (i, &p) in PitchClass::ALL.iter().enumerate() {
+        assert_eq!(u8::from(p), i as u8);
+    }
+    for key in 0..=127 {
+        let (p, octave) = key_to_pitch_class_octave(key);
+        assert_eq!(u8::from(p), pitch_class(key));
+        assert!((-1..=9).contains(&octave));
+        assert_eq!(pitch_class_octave_to_key(p, octave), Some(key));
+    }
+    for p in PitchClass::ALL {
+        for octave in i8::MIN..=i8::MAX {
+            let key = pitch_class_octave_to_key(p, octave);
+            match key {
+                Some(k) => assert_eq!(key_to_pitch_class_octave(k), (p, octave)),
+                None => assert!(!(-1..=9).contains(&octave) || (octave == 9 && p > PitchClass::G)),
+            }
+        }
+    }
+    assert_eq!(key_to_pitch_class_octave(127), (PitchClass::G, 9));
+}
+
+/// Color of a piano key, as returned by [piano_key_color].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyColor {
+    /// A white key: the naturals C, D, E, F, G, A and B.
+    White,
+    /// A black key: the sharps and flats C♯/D♭, D♯/E♭,
+    /// F♯/G♭, G♯/A♭ and A♯/B♭.
+    Black,
+}
+
+/// Bit `n` is set if pitch class `n` is a black key: C♯, D♯,
+/// F♯, G♯ and A♯.
+const BLACK_KEYS: u16 = 0b0101_0100_1010;
+
+/// Returns `true` if the given midi key is a black key on a
+/// piano keyboard.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::is_black_key;
+/// assert!(is_black_key(61));
+/// assert!(!is_black_key(60));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn is_black_key(key: u8) -> bool {
+    (BLACK_KEYS >> pitch_class(key)) & 1 != 0
+}
+
+/// Returns `true` if the given midi key is a white key on a
+/// piano keyboard.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::is_white_key;
+/// assert!(is_white_key(60));
+/// assert!(!is_white_key(61));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn is_white_key(key: u8) -> bool {
+    !is_black_key(key)
+}
+
+/// Returns the color of the given midi key on a piano
+/// keyboard.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{piano_key_color, KeyColor};
+/// assert_eq!(piano_key_color(70), KeyColor::Black);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn piano_key_color(key: u8) -> KeyColor {
+    if is_black_key(key) {
+        KeyColor::Black
+    } else {
+        KeyColor::White
+    }
+}
+
+#[test]
+fn test_key_color() {
+    for key in 0..=127 {
+        // Black keys are exactly those spelled with a sharp.
+        let black = key_to_note_name(key).contains('#');
+        assert_eq!(is_black_key(key), black, "{}", key);
+        assert_eq!(is_white_key(key), !black, "{}", key);
+        let color = if black {
+            KeyColor::Black
+        } else {
+            KeyColor::White
+        };
+        assert_eq!(piano_key_color(key), color);
+    }
+    let blacks: [u8; 5] = [1, 3, 6, 8, 10];
+    for pc in 0..12 {
+        assert_eq!(is_black_key(60 + pc), blacks.contains(&pc), "{}", pc);
+    }
+}
+
+/// Lowest and highest keys of a standard 88-key piano, A0
+/// and C8.
+const PIANO_KEYS: core::ops::RangeInclusive<u8> = 21..=108;
+
+/// Returns the 0-based index of the given midi key on a
+/// standard 88-key piano, from 0 for A0 (key 21) to 87 for
+/// C8 (key 108). Keys off the piano give `None`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::piano_key_index;
+/// assert_eq!(piano_key_index(60), Some(39));
+/// assert_eq!(piano_key_index(20), None);
+/// ```
+pub fn piano_key_index(key: u8) -> Option<u8> {
+    PIANO_KEYS.contains(&key).then(|| key - PIANO_KEYS.start())
+}
+
+/// Returns the 0-based index of the given midi key among the
+/// 52 white keys of a standard 88-key piano, from 0 for A0
+/// (key 21) to 51 for C8 (key 108). Black keys and keys off
+/// the piano give `None`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::piano_white_key_index;
+/// assert_eq!(piano_white_key_index(60), Some(23));
+/// assert_eq!(piano_white_key_index(61), None);
+/// ```
+pub fn piano_white_key_index(key: u8) -> Option<u8> {
+    // Number of white pitch classes below each pitch class.
+    const WHITE_BELOW: [u8; 12] = [0, 1, 1, 2, 2, 3, 4, 4, 5, 5, 6, 6];
+    let rank = |k: u8| 7 * (k / 12) + WHITE_BELOW[k as usize % 12];
+
+    piano_key_index(key)?;
+    is_white_key(key).then(|| rank(key) - rank(*PIANO_KEYS.start()))
+}
+
+#[test]
+fn test_piano_key_index() {
+    assert_eq!(piano_key_index(21), Some(0));
+    assert_eq!(piano_key_index(60), Some(39));
+    assert_eq!(piano_key_index(108), Some(87));
+    assert_eq!(piano_key_index(109), None);
+    assert_eq!(piano_key_index(255), None);
+
+    assert_eq!(piano_white_key_index(21), Some(0));
+    assert_eq!(piano_white_key_index(23), Some(1));
+    assert_eq!(piano_white_key_index(108), Some(51));
+    let mut next = 0;
+    for key in 0..=255 {
+        let index = piano_white_key_index(key);
+        if piano_key_index(key).is_some() && is_white_key(key) {
+            assert_eq!(index, Some(next), "{}", key);
+            next += 1;
+        } else {
+            assert_eq!(index, None, "{}", key);
+        }
+    }
+    assert_eq!(next, 52);
+}
+
+/// Error returned when parsing a note name fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseNoteError {
+    /// The note letter is not a known note letter.
+    UnknownLetter(char),
+    /// The character after the note letter is neither an
+    /// accidental nor the start of an octave number.
+    InvalidAccidental(char),
+    /// The octave number is outside the range of midi keys,
+    /// or the note lies outside that range in its octave.
+    OctaveOutOfRange,
+    /// The name is empty, or has a missing or malformed
+    /// octave number.
+    Malformed,
+}
+
+impl core::fmt::Display for ParseNoteError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseNoteError::UnknownLetter(c) => write!(f, "unknown note letter {:?}", c),
+            ParseNoteError::InvalidAccidental(c) => write!(f, "invalid accidental {:?}", c),
+            ParseNoteError::OctaveOutOfRange => write!(f, "octave out of range"),
+            ParseNoteError::Malformed => write!(f, "malformed note name"),
+        }
+    }
+}
+
+impl core::error::Error for ParseNoteError {}
+
+/// Pitch class of an English note letter.
+fn letter_english(letter: char) -> Option<i32> {
+    match letter.to_ascii_uppercase() {
+        'C' => Some(0),
+        'D' => Some(2),
+        'E' => Some(4),
+        'F' => Some(5),
+        'G' => Some(7),
+        'A' => Some(9),
+        'B' => Some(11),
+        _ => None,
+    }
+}
+
+/// Pitch class of a German note letter, where H is B
+/// natural and B is B flat.
+fn letter_german(letter: char) -> Option<i32> {
+    match letter.to_ascii_uppercase() {
+        'B' => Some(10),
+        'H' => Some(11),
+        _ => letter_english(letter),
+    }
+}
+
+/// Accidentals accepted after a note letter, with their
+/// offsets in semitones. Double flats come before flats so
+/// that the longest match is found first.
+const ACCIDENTALS: [(&str, i32); 9] = [
+    ("bb", -2),
+    ("\u{266d}\u{266d}", -2),
+    ("\u{1d12b}", -2),
+    ("b", -1),
+    ("\u{266d}", -1),
+    ("#", 1),
+    ("\u{266f}", 1),
+    ("x", 2),
+    ("\u{1d12a}", 2),
+];
+
+/// Parse a note name in scientific pitch notation, using
+/// `letter` to find the pitch class of the note letter.
+fn parse_note(name: &str, letter: fn(char) -> Option<i32>) -> Result<u8, ParseNoteError> {
+    let mut chars = name.chars();
+    let l = chars.next().ok_or(ParseNoteError::Malformed)?;
+    let pitch_class = letter(l).ok_or(ParseNoteError::UnknownLetter(l))?;
+
+    let rest = chars.as_str();
+    let (accidental, octave) = match ACCIDENTALS.iter().find(|(a, _)| rest.starts_with(a)) {
+        Some(&(a, offset)) => (offset, &rest[a.len()..]),
+        None => match rest.chars().next() {
+            Some(c) if c == '-' || c.is_ascii_digit() => (0, rest),
+            Some(c) => return Err(ParseNoteError::InvalidAccidental(c)),
+            None => return Err(ParseNoteError::Malformed),
+        },
+    };
+
+    if let Some(c) = octave.chars().next()
+        && ACCIDENTALS.iter().any(|(a, _)| a.starts_with(c))
+    {
+        // Only a single accidental is allowed.
+        return Err(ParseNoteError::InvalidAccidental(c));
+    }
+    let digits = octave.strip_prefix('-').unwrap_or(octave);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParseNoteError::Malformed);
+    }
+    let octave: i32 = octave
+        .parse()
+        .map_err(|_| ParseNoteError::OctaveOutOfRange)?;
+    if !(-1..=9).contains(&octave) {
+        return Err(ParseNoteError::OctaveOutOfRange);
+    }
+
+    let key = 12 * (octave + 1) + pitch_class + accidental;
+    if !(0..=127).contains(&key) {
+        return Err(ParseNoteError::OctaveOutOfRange);
+    }
+    Ok(key as u8)
+}
+
+/// Parses a note name in scientific pitch notation, such as
+/// "A4", "F#2" or "Bb-1", returning the midi key. Middle C
+/// (key 60) is "C4", so key 0 is "C-1". The note letter may
+/// be upper or lower case, and may be followed by a single
+/// accidental:
+///
+/// | accidental   | spellings        |
+/// |--------------|------------------|
+/// | sharp        | `#`, `♯`         |
+/// | flat         | `b`, `♭`         |
+/// | double sharp | `x`, `𝄪`         |
+/// | double flat  | `bb`, `♭♭`, `𝄫`  |
+///
+/// No allocation is done, so this is available without
+/// `std`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{parse_note_name, ParseNoteError};
+/// assert_eq!(parse_note_name("A4"), Ok(69));
+/// assert_eq!(parse_note_name("Bb3"), Ok(58));
+/// assert_eq!(parse_note_name("F♯2"), Ok(42));
+/// assert_eq!(parse_note_name("Cx4"), Ok(62));
+/// assert_eq!(parse_note_name("X4"), Err(ParseNoteError::UnknownLetter('X')));
+/// ```
+pub fn parse_note_name(name: &str) -> Result<u8, ParseNoteError> {
+    parse_note(name, letter_english)
+}
+
+/// Parses a note name as [parse_note_name] does, but using
+/// German note letters: "H" is B natural and "B" is B flat.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::parse_note_name_german;
+/// assert_eq!(parse_note_name_german("H3"), Ok(59));
+/// assert_eq!(parse_note_name_german("B3"), Ok(58));
+/// ```
+pub fn parse_note_name_german(name: &str) -> Result<u8, ParseNoteError> {
+    parse_note(name, letter_german)
+}
+
+/// Returns the name of the given midi key in Helmholtz
+/// pitch notation. Middle C (key 60) is "c'", the octave
+/// below it is "c", the next two below are "C" and "C,",
+/// and the octave above is "c''". Each further octave adds
+/// a prime above or a comma below. Black keys are spelled
+/// with sharps, as in [key_to_note_name]. Key 0 is "C,,,"
+/// and key 127 is "g''''''".
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_helmholtz;
+/// assert_eq!(key_to_helmholtz(69), "a'");
+/// assert_eq!(key_to_helmholtz(48), "c");
+/// assert_eq!(key_to_helmholtz(25), "C#,");
+/// assert_eq!(key_to_helmholtz(74), "d''");
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+#[cfg(feature = "std")]
+pub fn key_to_helmholtz(key: u8) -> String {
+    let key = or_panic(check_key(key));
+    let octave = (key / 12) as usize;
+    let note = key_to_note_name(key).trim_end_matches(|c: char| c == '-' || c.is_ascii_digit());
+    // Octave numbers here count from 0 for key 0, so that
+    // middle C is in octave 5.
+    if octave >= 4 {
+        note.to_ascii_lowercase() + &"'".repeat(octave - 4)
+    } else {
+        String::from(note) + &",".repeat(3 - octave)
+    }
+}
+
+/// Parses a note name in Helmholtz pitch notation, such as
+/// "a'", "c" or "F#,", returning the midi key. An upper
+/// case letter is in the octave two below middle C, and
+/// each following comma moves it down an octave; a lower
+/// case letter is in the octave below middle C, and each
+/// following prime (`'`) moves it up an octave. The letter
+/// may be followed by a single accidental, as accepted by
+/// [parse_note_name]; for example "bb'" is B♭ above middle
+/// C. Commas may only follow an upper case letter, and
+/// primes a lower case one.
+///
+/// No allocation is done, so this is available without
+/// `std`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{parse_helmholtz, ParseNoteError};
+/// assert_eq!(parse_helmholtz("a'"), Ok(69));
+/// assert_eq!(parse_helmholtz("c"), Ok(48));
+/// assert_eq!(parse_helmholtz("C,"), Ok(24));
+/// assert_eq!(parse_helmholtz("bb'"), Ok(70));
+/// assert_eq!(parse_helmholtz("c,"), Err(ParseNoteError::Malformed));
+/// ```
+pub fn parse_helmholtz(name: &str) -> Result<u8, ParseNoteError> {
+    let mut chars = name.chars();
+    let l = chars.next().ok_or(ParseNoteError::Malformed)?;
+    let pitch_class = letter_english(l).ok_or(ParseNoteError::UnknownLetter(l))?;
+
+    let rest = chars.as_str();
+    let (accidental, marks) = match ACCIDENTALS.iter().find(|(a, _)| rest.starts_with(a)) {
+        Some(&(a, offset)) => (offset, &rest[a.len()..]),
+        None => match rest.chars().next() {
+            None | Some('\'' | ',') => (0, rest),
+            Some(c) => return Err(ParseNoteError::InvalidAccidental(c)),
+        },
+    };
+
+    if let Some(c) = marks.chars().next()
+        && ACCIDENTALS.iter().any(|(a, _)| a.starts_with(c))
+    {
+        // Only a single accidental is allowed.
+        return Err(ParseNoteError::InvalidAccidental(c));
+    }
+    let (mark, direction, base) = if l.is_ascii_lowercase() {
+        ('\'', 1, 3)
+    } else {
+        (',', -1, 2)
+    };
+    if !marks.chars().all(|c| c == mark) {
+        return Err(ParseNoteError::Malformed);
+    }
+    // Avoid overflow on absurdly long names.
+    let count = marks.len().min(12) as i32;
+    let octave = base + direction * count;
+    if !(-1..=9).contains(&octave) {
+        return Err(ParseNoteError::OctaveOutOfRange);
+    }
+
+    let key = 12 * (octave + 1) + pitch_class + accidental;
+    if !(0..=127).contains(&key) {
+        return Err(ParseNoteError::OctaveOutOfRange);
+    }
+    Ok(key as u8)
+}
+
+#[test]
+fn test_helmholtz() {
+    let tests: &[(u8, &str)] = &[
+        (0, "C,,,"),
+        (11, "B,,,"),
+        (12, "C,,"),
+        (24, "C,"),
+        (36, "C"),
+        (47, "B"),
+        (48, "c"),
+        (59, "b"),
+        (60, "c'"),
+        (61, "c#'"),
+        (69, "a'"),
+        (72, "c''"),
+        (84, "c'''"),
+        (96, "c''''"),
+        (108, "c'''''"),
+        (120, "c''''''"),
+        (127, "g''''''"),
+    ];
+    for &(key, name) in tests {
+        #[cfg(feature = "std")]
+        assert_eq!(key_to_helmholtz(key), name);
+        assert_eq!(parse_helmholtz(name), Ok(key));
+    }
+
+    #[cfg(feature = "std")]
+    for k in 0..=127 {
+        let name = key_to_helmholtz(k);
+        assert_eq!(parse_helmholtz(&name), Ok(k), "{}", name);
+        let letter = name.chars().next().unwrap();
+        assert_eq!(letter.is_ascii_lowercase(), k >= 48, "{}", name);
+    }
+
+    let errors: &[(&str, ParseNoteError)] = &[
+        ("", ParseNoteError::Malformed),
+        ("H", ParseNoteError::UnknownLetter('H')),
+        ("c$'", ParseNoteError::InvalidAccidental('$')),
+        ("c#b'", ParseNoteError::InvalidAccidental('b')),
+        ("c,", ParseNoteError::Malformed),
+        ("C'", ParseNoteError::Malformed),
+        ("c',", ParseNoteError::Malformed),
+        ("c'4", ParseNoteError::Malformed),
+        ("C,,,,", ParseNoteError::OctaveOutOfRange),
+        ("Cb,,,", ParseNoteError::OctaveOutOfRange),
+        ("g#''''''", ParseNoteError::OctaveOutOfRange),
+        ("c'''''''", ParseNoteError::OctaveOutOfRange),
+        (
+            "c''''''''''''''''''''''''",
+            ParseNoteError::OctaveOutOfRange,
+        ),
+    ];
+    for (name, err) in errors {
+        assert_eq!(parse_helmholtz(name), Err(*err), "{}", name);
+    }
+    assert_eq!(parse_helmholtz("b\u{266d}'"), Ok(70));
+    assert_eq!(parse_helmholtz("Fx,"), Ok(31));
+}
+
+#[test]
+fn test_key_to_note_name() {
+    let tests: &[(u8, &str, &str)] = &[
+        (0, "C-1", "C-1"),
+        (1, "C#-1", "Db-1"),
+        (11, "B-1", "B-1"),
+        (12, "C0", "C0"),
+        (60, "C4", "C4"),
+        (61, "C#4", "Db4"),
+        (63, "D#4", "Eb4"),
+        (66, "F#4", "Gb4"),
+        (68, "G#4", "Ab4"),
+        (69, "A4", "A4"),
+        (70, "A#4", "Bb4"),
+        (127, "G9", "G9"),
+    ];
+    for &(key, sharp, flat) in tests {
+        assert_eq!(key_to_note_name(key), sharp);
+        assert_eq!(key_to_note_name_flat(key), flat);
+    }
+}
+
+#[test]
+fn test_key_to_note_name_with_preference() {
+    use AccidentalPreference::*;
+    assert_eq!(key_to_note_name_with_preference(70, Sharp), "A#4");
+    assert_eq!(key_to_note_name_with_preference(70, Flat), "Bb4");
+    assert_eq!(AccidentalPreference::default(), Sharp);
+
+    let mut buf = [0; MAX_NOTE_NAME_LEN];
+    for k in 0..=127 {
+        assert_eq!(
+            key_to_note_name_with_preference(k, Sharp),
+            key_to_note_name(k)
+        );
+        assert_eq!(
+            key_to_note_name_with_preference(k, Flat),
+            key_to_note_name_flat(k)
+        );
+        for pref in [Sharp, Flat] {
+            let spelled = key_to_name_spelled(k, pref.into(), &mut buf).unwrap();
+            assert_eq!(spelled, key_to_note_name_with_preference(k, pref));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "key 128 out of range 0..=127")]
+fn test_key_to_note_name_with_preference_out_of_range() {
+    key_to_note_name_with_preference(128, AccidentalPreference::Flat);
+}
+
+#[test]
+fn test_parse_note_name() {
+    for k in 0..=127 {
+        assert_eq!(parse_note_name(key_to_note_name(k)), Ok(k));
+        assert_eq!(parse_note_name(key_to_note_name_flat(k)), Ok(k));
+    }
+
+    let tests: &[(&str, Result<u8, ParseNoteError>)] = &[
+        ("C4", Ok(60)),
+        ("c4", Ok(60)),
+        ("F#2", Ok(42)),
+        ("A#4", Ok(70)),
+        ("Bb-1", Ok(10)),
+        ("B#3", Ok(60)),
+        ("Cb4", Ok(59)),
+        ("H4", Err(ParseNoteError::UnknownLetter('H'))),
+        ("A$4", Err(ParseNoteError::InvalidAccidental('$'))),
+        ("A##4", Err(ParseNoteError::InvalidAccidental('#'))),
+        ("A#b4", Err(ParseNoteError::InvalidAccidental('b'))),
+        ("Abbb4", Err(ParseNoteError::InvalidAccidental('b'))),
+        ("Cx4", Ok(62)),
+        ("cx4", Ok(62)),
+        ("Dbb4", Ok(60)),
+        ("bb4", Ok(70)),
+        ("Bbb4", Ok(69)),
+        ("C\u{266f}4", Ok(61)),
+        ("D\u{266d}4", Ok(61)),
+        ("E\u{266d}\u{266d}4", Ok(62)),
+        ("F\u{1d12a}4", Ok(67)),
+        ("G\u{1d12b}4", Ok(65)),
+        ("B\u{266f}9", Err(ParseNoteError::OctaveOutOfRange)),
+        ("Gx9", Err(ParseNoteError::OctaveOutOfRange)),
+        ("Dbb-1", Ok(0)),
+        ("Cbb-1", Err(ParseNoteError::OctaveOutOfRange)),
+        ("C\u{266f}", Err(ParseNoteError::Malformed)),
+        ("A10", Err(ParseNoteError::OctaveOutOfRange)),
+        ("A-2", Err(ParseNoteError::OctaveOutOfRange)),
+        ("G#9", Err(ParseNoteError::OctaveOutOfRange)),
+        ("Cb-1", Err(ParseNoteError::OctaveOutOfRange)),
+        ("A99999999999", Err(ParseNoteError::OctaveOutOfRange)),
+        ("", Err(ParseNoteError::Malformed)),
+        ("A", Err(ParseNoteError::Malformed)),
+        ("A#", Err(ParseNoteError::Malformed)),
+        ("A-", Err(ParseNoteError::Malformed)),
+        ("A4x", Err(ParseNoteError::Malformed)),
+        ("A+4", Err(ParseNoteError::InvalidAccidental('+'))),
+    ];
+    for &(name, result) in tests {
+        assert_eq!(parse_note_name(name), result, "{}", name);
+    }
+}
+
+#[test]
+fn test_parse_note_name_german() {
+    let tests: &[(&str, Result<u8, ParseNoteError>)] = &[
+        ("C4", Ok(60)),
+        ("H4", Ok(71)),
+        ("B4", Ok(70)),
+        ("h4", Ok(71)),
+        ("A4", Ok(69)),
+        ("X4", Err(ParseNoteError::UnknownLetter('X'))),
+    ];
+    for &(name, result) in tests {
+        assert_eq!(parse_note_name_german(name), result, "{}", name);
+    }
+}
+
+/// Error returned when formatting a note name fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameError {
+    /// The given key is not in the range `0..=127`.
+    OutOfRange {
+        /// The offending key.
+        key: u8,
+    },
+    /// The buffer is too small to hold the name.
+    BufferTooSmall {
+        /// The number of bytes the name needs.
+        needed: usize,
+    },
+}
+
+impl core::fmt::Display for NameError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            NameError::OutOfRange { key } => write!(f, "key {} out of range 0..=127", key),
+            NameError::BufferTooSmall { needed } => {
+                write!(f, "buffer too small for note name: {} bytes needed", needed)
+            }
+        }
+    }
+}
+
+impl core::error::Error for NameError {}
+
+impl From<KeyError> for NameError {
+    fn from(err: KeyError) -> Self {
+        match err {
+            KeyError::OutOfRange { key } => NameError::OutOfRange { key },
+        }
+    }
+}
+
+/// Longest note name written by [key_to_name], which is
+/// that of a sharp in octave -1, such as "C#-1".
+pub const MAX_NOTE_NAME_LEN: usize = 4;
+
+/// Choice of spelling for notes that can be named more than
+/// one way, as used by [key_to_name_spelled].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Spelling {
+    /// Black keys are spelled with sharps: "C#", "D#", "F#",
+    /// "G#" and "A#".
+    Sharps,
+    /// Black keys are spelled with flats: "Db", "Eb", "Gb",
+    /// "Ab" and "Bb".
+    Flats,
+    /// Notes are spelled as in the major key with the given
+    /// key signature: a positive count of sharps or a
+    /// negative count of flats, in the range `-7..=7`. Notes
+    /// of the key are spelled with its letters, so that in
+    /// F♯ major (6 sharps) pitch class 5 is "E#"; other black
+    /// keys are spelled with sharps in sharp keys and flats
+    /// in flat keys.
+    KeySignature(i8),
+}
+
+/// Note letters, in order from C.
+const LETTERS: [u8; 7] = *b"CDEFGAB";
+
+/// Pitch classes of the natural note [LETTERS].
+const LETTER_PITCH_CLASSES: [u8; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+/// Position of each of the [LETTERS] in the order in which
+/// sharps are added to a key signature: F C G D A E B. Flats
+/// are added in the reverse order.
+const SHARP_ORDER: [i8; 7] = [1, 3, 5, 0, 2, 4, 6];
+
+/// Spell pitch class `m` as an index into [LETTERS] and an
+/// accidental in semitones.
+fn spell(m: u8, spelling: Spelling) -> (usize, i8) {
+    let black = is_black_key(m);
+    match spelling {
+        Spelling::Sharps if black => (letter_index(m - 1), 1),
+        Spelling::Flats if black => (letter_index(m + 1), -1),
+        Spelling::Sharps | Spelling::Flats => (letter_index(m), 0),
+        Spelling::KeySignature(n) => {
+            assert!(
+                (-7..=7).contains(&n),
+                "key signature {} out of range -7..=7",
+                n,
+            );
+            for (i, &order) in SHARP_ORDER.iter().enumerate() {
+                let accidental = if order < n {
+                    1
+                } else if 6 - order < -n {
+                    -1
+                } else {
+                    0
+                };
+                let class = LETTER_PITCH_CLASSES[i] as i8 + accidental;
+                if class.rem_euclid(12) == m as i8 {
+                    return (i, accidental);
+                }
+            }
+            spell(
+                m,
+                if n < 0 {
+                    Spelling::Flats
+                } else {
+                    Spelling::Sharps
+                },
+            )
+        }
+    }
+}
+
+/// Index into [LETTERS] of the natural pitch class `m`.
+fn letter_index(m: u8) -> usize {
+    LETTER_PITCH_CLASSES
+        .iter()
+        .position(|&c| c == m)
+        .expect("pitch class is natural")
+}
+
+/// Writes the name of the given midi key in scientific
+/// pitch notation into `buf`, as [key_to_note_name] spells
+/// it, and returns the part of `buf` holding the name. No
+/// allocation is done, so this is available without `std`;
+/// a buffer of [MAX_NOTE_NAME_LEN] bytes is always large
+/// enough.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{key_to_name, NameError};
+/// let mut buf = [0; 4];
+/// assert_eq!(key_to_name(69, &mut buf), Ok("A4"));
+/// assert_eq!(key_to_name(42, &mut buf), Ok("F#2"));
+/// assert_eq!(
+///     key_to_name(1, &mut buf[..3]),
+///     Err(NameError::BufferTooSmall { needed: 4 }),
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Returns [NameError::OutOfRange] if `key` is not in the
+/// range `0..=127`, and [NameError::BufferTooSmall] if the
+/// name does not fit in `buf`.
+pub fn key_to_name(key: u8, buf: &mut [u8]) -> Result<&str, NameError> {
+    key_to_name_spelled(key, Spelling::Sharps, buf)
+}
+
+/// Writes the name of the given midi key into `buf` as
+/// [key_to_name] does, followed by a NUL byte for C
+/// interoperability, and returns the name without the NUL.
+/// The buffer always has room for the longest name and its
+/// terminator, so only an out-of-range key can fail.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_note_name_buf;
+/// let mut buf = [0xff; 5];
+/// assert_eq!(key_to_note_name_buf(1, &mut buf), "C#-1");
+/// assert_eq!(buf, *b"C#-1\0");
+/// assert_eq!(key_to_note_name_buf(69, &mut buf), "A4");
+/// assert_eq!(&buf[..3], b"A4\0");
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_note_name_buf(key: u8, buf: &mut [u8; MAX_NOTE_NAME_LEN + 1]) -> &str {
+    let len = match key_to_name(key, &mut buf[..MAX_NOTE_NAME_LEN]) {
+        Ok(name) => name.len(),
+        Err(e) => panic!("{}", e),
+    };
+    buf[len] = 0;
+    core::str::from_utf8(&buf[..len]).expect("note names are ASCII")
+}
+
+/// Writes the name of the given midi key into `buf` as
+/// [key_to_name] does, but spelled as `spelling` directs.
+/// Every name written is accepted by [parse_note_name].
+///
+/// A note spelled with a letter from a neighboring octave
+/// takes that octave's number, so that B♯3 and C♭4 are keys
+/// 60 and 59. Key 0 is always "C-1", since there is no
+/// octave -2.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{key_to_name_spelled, Spelling};
+/// let mut buf = [0; 4];
+/// assert_eq!(key_to_name_spelled(61, Spelling::Flats, &mut buf), Ok("Db4"));
+/// // Bb is in F major, which has one flat.
+/// assert_eq!(
+///     key_to_name_spelled(70, Spelling::KeySignature(-1), &mut buf),
+///     Ok("Bb4"),
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Returns [NameError::OutOfRange] if `key` is not in the
+/// range `0..=127`, and [NameError::BufferTooSmall] if the
+/// name does not fit in `buf`.
+///
+/// # Panics
+///
+/// Panics if `spelling` is a key signature outside the
+/// range `-7..=7`.
+pub fn key_to_name_spelled(key: u8, spelling: Spelling, buf: &mut [u8]) -> Result<&str, NameError> {
+    let (m, o) = try_key_to_params_bottom(key)?;
+    let (mut letter, mut accidental) = spell(m, spelling);
+    // The octave number is one less than `o`, adjusted when
+    // the letter's natural is in a neighboring octave.
+    let mut octave = o as i8 - 1 - (LETTER_PITCH_CLASSES[letter] as i8 + accidental).div_euclid(12);
+    if octave < -1 {
+        (letter, accidental, octave) = (0, 0, -1);
+    }
+
+    let accidental: &[u8] = match accidental {
+        -1 => b"b",
+        1 => b"#",
+        _ => b"",
+    };
+    let octave: &[u8] = if octave < 0 {
+        b"-1"
+    } else {
+        &[b'0' + octave as u8]
+    };
+
+    let needed = 1 + accidental.len() + octave.len();
+    let Some(name) = buf.get_mut(..needed) else {
+        return Err(NameError::BufferTooSmall { needed });
+    };
+    name[0] = LETTERS[letter];
+    let (sign, number) = name[1..].split_at_mut(accidental.len());
+    sign.copy_from_slice(accidental);
+    number.copy_from_slice(octave);
+    Ok(core::str::from_utf8(name).expect("note names are ASCII"))
+}
+
+#[test]
+fn test_key_to_note_name_buf() {
+    let mut buf = [0xff; 5];
+    for k in 0..=127 {
+        let name = key_to_note_name_buf(k, &mut buf);
+        assert_eq!(name, key_to_note_name(k));
+        let len = name.len();
+        assert_eq!(buf[len], 0);
+        assert!(!buf[..len].contains(&0));
+    }
+}
+
+#[test]
+#[should_panic(expected = "key 128 out of range 0..=127")]
+fn test_key_to_note_name_buf_panic() {
+    key_to_note_name_buf(128, &mut [0; 5]);
+}
+
+#[test]
+fn test_key_to_name() {
+    let mut buf = [0; MAX_NOTE_NAME_LEN];
+    for k in 0..=127 {
+        let name = key_to_name(k, &mut buf).unwrap();
+        assert_eq!(name, key_to_note_name(k));
+        assert_eq!(parse_note_name(name), Ok(k));
+    }
+
+    let mut big = [b'?'; 8];
+    assert_eq!(key_to_name(127, &mut big), Ok("G9"));
+    assert_eq!(&big[2..], b"??????");
+
+    assert_eq!(key_to_name(60, &mut buf[..2]), Ok("C4"));
+    assert_eq!(
+        key_to_name(61, &mut buf[..2]),
+        Err(NameError::BufferTooSmall { needed: 3 }),
+    );
+    assert_eq!(
+        key_to_name(0, &mut []),
+        Err(NameError::BufferTooSmall { needed: 3 }),
+    );
+    assert_eq!(
+        key_to_name(128, &mut buf),
+        Err(NameError::OutOfRange { key: 128 }),
+    );
+}
+
+#[test]
+fn test_key_to_name_spelled() {
+    let mut buf = [0; MAX_NOTE_NAME_LEN];
+    for k in 0..=127 {
+        let sharp = key_to_name_spelled(k, Spelling::Sharps, &mut buf).unwrap();
+        assert_eq!(sharp, key_to_note_name(k));
+        let flat = key_to_name_spelled(k, Spelling::Flats, &mut buf).unwrap();
+        assert_eq!(flat, key_to_note_name_flat(k));
+        for n in -7..=7 {
+            let name = key_to_name_spelled(k, Spelling::KeySignature(n), &mut buf).unwrap();
+            assert_eq!(parse_note_name(name), Ok(k), "{} {}", n, name);
+        }
+    }
+
+    // One octave of pitch classes from C4, under each
+    // signature.
+    let tests: &[(i8, [&str; 12])] = &[
+        (
+            0,
+            [
+                "C4", "C#4", "D4", "D#4", "E4", "F4", "F#4", "G4", "G#4", "A4", "A#4", "B4",
+            ],
+        ),
+        (
+            -1,
+            [
+                "C4", "Db4", "D4", "Eb4", "E4", "F4", "Gb4", "G4", "Ab4", "A4", "Bb4", "B4",
+            ],
+        ),
+        (
+            2,
+            [
+                "C4", "C#4", "D4", "D#4", "E4", "F4", "F#4", "G4", "G#4", "A4", "A#4", "B4",
+            ],
+        ),
+        (
+            -3,
+            [
+                "C4", "Db4", "D4", "Eb4", "E4", "F4", "Gb4", "G4", "Ab4", "A4", "Bb4", "B4",
+            ],
+        ),
+        (
+            6,
+            [
+                "C4", "C#4", "D4", "D#4", "E4", "E#4", "F#4", "G4", "G#4", "A4", "A#4", "B4",
+            ],
+        ),
+        (
+            7,
+            [
+                "B#3", "C#4", "D4", "D#4", "E4", "E#4", "F#4", "G4", "G#4", "A4", "A#4", "B4",
+            ],
+        ),
+        (
+            -6,
+            [
+                "C4", "Db4", "D4", "Eb4", "E4", "F4", "Gb4", "G4", "Ab4", "A4", "Bb4", "Cb5",
+            ],
+        ),
+        (
+            -7,
+            [
+                "C4", "Db4", "D4", "Eb4", "Fb4", "F4", "Gb4", "G4", "Ab4", "A4", "Bb4", "Cb5",
+            ],
+        ),
+    ];
+    for (n, names) in tests {
+        for (k, name) in (60..).zip(names) {
+            let spelled = key_to_name_spelled(k, Spelling::KeySignature(*n), &mut buf);
+            assert_eq!(spelled, Ok(*name), "{} {}", n, k);
+        }
+    }
+
+    let spelled = key_to_name_spelled(0, Spelling::KeySignature(7), &mut buf);
+    assert_eq!(spelled, Ok("C-1"));
+    let spelled = key_to_name_spelled(12, Spelling::KeySignature(7), &mut buf);
+    assert_eq!(spelled, Ok("B#-1"));
+    let spelled = key_to_name_spelled(119, Spelling::KeySignature(-6), &mut buf);
+    assert_eq!(spelled, Ok("Cb9"));
+}
+
+#[test]
+#[should_panic(expected = "key signature 8 out of range -7..=7")]
+fn test_key_to_name_spelled_bad_signature() {
+    key_to_name_spelled(60, Spelling::KeySignature(8), &mut [0; 4]).unwrap();
+}
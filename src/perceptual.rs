@@ -0,0 +1,193 @@
+//! Perceptual pitch scales.
+
+#[cfg(all(not(feature = "std"), not(test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::{frequency_to_key_saturating, key_to_frequency_raw};
+
+/// Computes the pitch in mels of the given frequency in Hz,
+/// using the HTK formula
+///    $$2595 \log_{10} \left(1 + \frac{f}{700}\right)$$
+/// This is the convention of HTK and of librosa with
+/// `htk=True`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::frequency_to_mel;
+/// assert_eq!(frequency_to_mel(1000.0).round(), 1000.0);
+/// ```
+pub fn frequency_to_mel(freq: f32) -> f32 {
+    2595.0 * f32::log10(1.0 + freq / 700.0)
+}
+
+/// Computes the frequency in Hz of the given pitch in mels.
+/// This is the inverse of [frequency_to_mel].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::mel_to_frequency;
+/// assert_eq!(mel_to_frequency(0.0), 0.0);
+/// ```
+pub fn mel_to_frequency(mel: f32) -> f32 {
+    700.0 * (f32::powf(10.0, mel / 2595.0) - 1.0)
+}
+
+/// Computes the pitch in mels of the given midi key. See
+/// [frequency_to_mel].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_mel;
+/// assert_eq!((key_to_mel(69) * 100.0).round(), 54964.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_mel(key: u8) -> f32 {
+    frequency_to_mel(key_to_frequency_raw(key))
+}
+
+/// Finds the midi key nearest in pitch to the given pitch in
+/// mels, clamping to the range `0..=127` as
+/// [frequency_to_key_saturating] does.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{key_to_mel, mel_to_key};
+/// assert_eq!(mel_to_key(key_to_mel(69)), 69);
+/// ```
+pub fn mel_to_key(mel: f32) -> u8 {
+    frequency_to_key_saturating(mel_to_frequency(mel))
+}
+
+/// Computes the critical-band rate in Bark of the given
+/// frequency in Hz, using the formula
+///    $$13 \arctan(0.00076 f) + 3.5 \arctan\left(\left(\frac{f}{7500}\right)^2\right)$$
+/// of Zwicker and Terhardt. The scale runs from 0 Bark at 0
+/// Hz toward about 25.9 Bark as the frequency grows.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::frequency_to_bark;
+/// assert_eq!(frequency_to_bark(1000.0).round(), 9.0);
+/// ```
+pub fn frequency_to_bark(freq: f32) -> f32 {
+    bark_f64(freq as f64) as f32
+}
+
+/// Computes the critical-band rate in Bark of the given midi
+/// key. See [frequency_to_bark].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_bark;
+/// assert_eq!(key_to_bark(69).round(), 4.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_bark(key: u8) -> f32 {
+    frequency_to_bark(key_to_frequency_raw(key))
+}
+
+/// Computes the frequency in Hz of the given critical-band
+/// rate in Bark. This is the inverse of [frequency_to_bark],
+/// which has no closed form: it is found by Newton-Raphson
+/// iteration from Traunmüller's approximate inverse.
+///
+/// Rates of 0 or less, or NaN, give 0 Hz. Rates at or above
+/// the top of the scale, about 25.9 Bark, give infinity.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{bark_to_frequency, frequency_to_bark};
+/// assert_eq!(bark_to_frequency(frequency_to_bark(440.0)).round(), 440.0);
+/// ```
+pub fn bark_to_frequency(bark: f32) -> f32 {
+    use core::f64::consts::FRAC_PI_2;
+
+    let bark = bark as f64;
+    if bark.is_nan() || bark <= 0.0 {
+        return 0.0;
+    }
+    if bark >= 16.5 * FRAC_PI_2 {
+        return f32::INFINITY;
+    }
+    let mut f = 1960.0 * (bark + 0.53) / (26.28 - bark);
+    for _ in 0..20 {
+        let x = 0.00076 * f;
+        let y = f / 7500.0;
+        let slope = 13.0 * 0.00076 / (1.0 + x * x) + 3.5 * 2.0 * y / 7500.0 / (1.0 + y * y * y * y);
+        let step = (bark_f64(f) - bark) / slope;
+        f -= step;
+        if f64::abs(step) <= 1.0e-9 * f {
+            break;
+        }
+    }
+    f as f32
+}
+
+/// Double-precision Bark formula of [frequency_to_bark].
+fn bark_f64(freq: f64) -> f64 {
+    let y = freq / 7500.0;
+    13.0 * f64::atan(0.00076 * freq) + 3.5 * f64::atan(y * y)
+}
+
+#[test]
+fn test_mel() {
+    // Reference values from librosa.hz_to_mel(f, htk=True).
+    assert!(f32::abs(key_to_mel(69) - 549.638_7) < 0.001);
+    assert!(f32::abs(frequency_to_mel(1000.0) - 999.985_6) < 0.001);
+
+    for key in 0..=127 {
+        let mel = key_to_mel(key);
+        assert_eq!(mel_to_key(mel), key);
+        let f = mel_to_frequency(mel);
+        let exact = key_to_frequency_raw(key);
+        assert!(
+            f32::abs(f - exact) < 1.0e-4 * exact,
+            "{} {} {}",
+            key,
+            f,
+            exact
+        );
+    }
+    assert_eq!(mel_to_key(-1.0), 0);
+    assert_eq!(mel_to_key(1.0e4), 127);
+}
+
+#[test]
+fn test_bark() {
+    // The extremes of the midi range sit near the ends of the
+    // Bark scale.
+    let low = key_to_bark(0);
+    assert!(low > 0.0 && low < 0.1, "{}", low);
+    let high = key_to_bark(127);
+    assert!(high > 23.0 && high < 24.0, "{}", high);
+
+    for key in 0..=127 {
+        let f = bark_to_frequency(key_to_bark(key));
+        let exact = key_to_frequency_raw(key);
+        assert!(
+            f32::abs(f - exact) < 1.0e-5 * exact,
+            "{} {} {}",
+            key,
+            f,
+            exact
+        );
+    }
+    assert_eq!(bark_to_frequency(0.0), 0.0);
+    assert_eq!(bark_to_frequency(f32::NAN), 0.0);
+    assert_eq!(bark_to_frequency(26.0), f32::INFINITY);
+    assert!(bark_to_frequency(25.9) > 20000.0);
+}
@@ -0,0 +1,448 @@
+//! Oscillator quantities, mostly relative to an audio
+//! sample rate.
+
+#[cfg(all(not(feature = "std"), not(test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use core::f32::consts::TAU;
+
+use crate::{
+    frequency_to_key, key_to_frequency_approx_raw, key_to_frequency_f64, key_to_frequency_raw,
+    key_to_period_approx_raw, key_to_period_raw,
+};
+
+/// Computes the per-sample phase increment, in cycles per
+/// sample, of an oscillator at the frequency of the given
+/// midi key when run at the given sample rate in Hz. This
+/// is just the frequency divided by the sample rate, so
+/// multiplying the result by the sample rate recovers the
+/// frequency.
+///
+/// A result above 0.5 means that the note is above the
+/// Nyquist frequency for the sample rate, and will alias.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_phase_increment;
+/// assert_eq!(key_to_phase_increment(69, 44000.0), 0.01);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_phase_increment(key: u8, sample_rate: f32) -> f32 {
+    key_to_frequency_raw(key) / sample_rate
+}
+
+/// Computes the approximate per-sample phase increment of an
+/// oscillator, as [key_to_phase_increment] but using
+/// [key_to_frequency_approx_raw].
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_phase_increment_approx(key: u8, sample_rate: f32) -> f32 {
+    key_to_frequency_approx_raw(key) / sample_rate
+}
+
+/// Computes the per-sample phase increment of a direct
+/// digital synthesis (DDS) oscillator with a 32-bit phase
+/// accumulator, as a Q0.32 fixed-point fraction of a cycle.
+/// This is $2^{32} f / r$ for key frequency $f$ and sample
+/// rate $r$ in Hz, rounded to nearest.
+///
+/// If the key frequency is at or above the sample rate the
+/// increment would overflow, and the result saturates at
+/// `u32::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_phase_increment_q32;
+/// assert_eq!(key_to_phase_increment_q32(69, 44000), 42949673);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_phase_increment_q32(key: u8, sample_rate: u32) -> u32 {
+    let inc = key_to_frequency_f64(key) * (1u64 << 32) as f64 / sample_rate as f64;
+    // Float-to-int casts saturate.
+    ((inc + 0.5) as u64).min(u32::MAX as u64) as u32
+}
+
+/// Computes the per-sample phase increment of a DDS
+/// oscillator with an `accumulator_bits`-bit phase
+/// accumulator: $2^N f / r$ for accumulator width $N$, key
+/// frequency $f$ and sample rate $r$ in Hz, rounded to
+/// nearest. With `accumulator_bits` of 32 this is
+/// [key_to_phase_increment_q32].
+///
+/// The phase wraps correctly when the increment is added
+/// with [u32::wrapping_add] and the accumulator is masked to
+/// its low `accumulator_bits` bits (a no-op at 32 bits).
+/// If the key frequency is at or above the sample rate the
+/// increment would not fit, and the result saturates at
+/// $2^N - 1$.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_phase_increment_fixed;
+/// assert_eq!(key_to_phase_increment_fixed(69, 44000.0, 24), 167772);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`, or if
+/// `accumulator_bits` is greater than 32.
+pub fn key_to_phase_increment_fixed(key: u8, sample_rate: f32, accumulator_bits: u8) -> u32 {
+    assert!(
+        accumulator_bits <= 32,
+        "accumulator bits {} out of range 0..=32",
+        accumulator_bits,
+    );
+    key_to_phase_increment_fixed_f64(key, sample_rate as f64, accumulator_bits) as u32
+}
+
+/// Version of [key_to_phase_increment_fixed] for phase
+/// accumulators up to 64 bits wide, such as the 48-bit
+/// accumulators of many DDS chips. The computation is done
+/// in double precision, so increments wider than 53 bits
+/// are rounded to 53 significant bits.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_phase_increment_fixed_f64;
+/// assert_eq!(key_to_phase_increment_fixed_f64(69, 44000.0, 48), 2814749767107);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`, or if
+/// `accumulator_bits` is greater than 64.
+pub fn key_to_phase_increment_fixed_f64(key: u8, sample_rate: f64, accumulator_bits: u8) -> u64 {
+    assert!(
+        accumulator_bits <= 64,
+        "accumulator bits {} out of range 0..=64",
+        accumulator_bits,
+    );
+    let max = u64::MAX
+        .checked_shr(64 - accumulator_bits as u32)
+        .unwrap_or(0);
+    let scale = f64::powi(2.0, accumulator_bits as i32);
+    let inc = key_to_frequency_f64(key) * scale / sample_rate;
+    // Float-to-int casts saturate.
+    ((inc + 0.5) as u64).min(max)
+}
+
+/// Computes the cycle period, in samples, of the given midi
+/// key at the given sample rate in Hz. This is the unit
+/// period of [key_to_period_raw] multiplied by the sample rate,
+/// as needed for delay lines and wavetable lengths.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period_samples;
+/// assert_eq!(key_to_period_samples(69, 44000.0), 100.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_period_samples(key: u8, sample_rate: f32) -> f32 {
+    key_to_period_raw(key) * sample_rate
+}
+
+/// Computes the cycle period of the given midi key, rounded
+/// to a whole number of samples at the given sample rate in
+/// Hz, together with the resulting pitch error in cents. The
+/// error is positive when the rounded period is short, so
+/// that the note sounds sharp, and negative when it sounds
+/// flat.
+///
+/// The period is never less than one sample. For very high
+/// keys at low sample rates this gives a large error, which
+/// is reported as is.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_period_samples_rounded;
+/// let (period, cents) = key_to_period_samples_rounded(69, 44100);
+/// assert_eq!(period, 100);
+/// assert_eq!(cents.round(), 4.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_period_samples_rounded(key: u8, sample_rate: u32) -> (u32, f32) {
+    let freq = key_to_frequency_f64(key);
+    let sample_rate = sample_rate as f64;
+    // Float-to-int casts saturate.
+    let period = ((sample_rate / freq + 0.5) as u32).max(1);
+    let error = 1200.0 * f64::log2(sample_rate / period as f64 / freq);
+    (period, error as f32)
+}
+
+/// Computes the approximate cycle period in samples of the
+/// given midi key, as [key_to_period_samples] but using
+/// [key_to_period_approx_raw].
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_period_samples_approx(key: u8, sample_rate: f32) -> f32 {
+    key_to_period_approx_raw(key) * sample_rate
+}
+
+/// Computes the angular frequency $\omega = 2 \pi f$, in
+/// radians per second, of the given midi key.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_angular_frequency;
+/// use core::f32::consts::TAU;
+/// assert_eq!(key_to_angular_frequency(69), TAU * 440.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_angular_frequency(key: u8) -> f32 {
+    TAU * key_to_frequency_raw(key)
+}
+
+/// Computes the approximate angular frequency of the given
+/// midi key, as [key_to_angular_frequency] but using
+/// [key_to_frequency_approx_raw].
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_angular_frequency_approx(key: u8) -> f32 {
+    TAU * key_to_frequency_approx_raw(key)
+}
+
+/// Finds the midi key whose angular frequency is nearest to
+/// the given angular frequency `omega` in radians per
+/// second. This is the inverse of
+/// [key_to_angular_frequency], and rounds as
+/// [frequency_to_key] does.
+///
+/// Returns `None` if `omega` is not near a midi key or is
+/// not a positive number.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::angular_frequency_to_key;
+/// assert_eq!(angular_frequency_to_key(2764.6), Some(69));
+/// assert_eq!(angular_frequency_to_key(-1.0), None);
+/// ```
+pub fn angular_frequency_to_key(omega: f32) -> Option<u8> {
+    frequency_to_key(omega / TAU)
+}
+
+/// Computes the normalized angular frequency
+/// $\omega = 2 \pi f / r$, in radians per sample, of the
+/// given midi key at the given sample rate $r$ in Hz, as
+/// used in digital filter design.
+///
+/// The result is not wrapped: a result above $\pi$ means
+/// that the note is above the Nyquist frequency for the
+/// sample rate.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_normalized_angular_frequency;
+/// use core::f32::consts::PI;
+/// assert_eq!(key_to_normalized_angular_frequency(69, 88000.0), PI / 100.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_normalized_angular_frequency(key: u8, sample_rate: f32) -> f32 {
+    TAU * key_to_phase_increment(key, sample_rate)
+}
+
+/// Computes the approximate normalized angular frequency of
+/// the given midi key, as
+/// [key_to_normalized_angular_frequency] but using
+/// [key_to_frequency_approx_raw].
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_normalized_angular_frequency_approx(key: u8, sample_rate: f32) -> f32 {
+    TAU * key_to_phase_increment_approx(key, sample_rate)
+}
+
+#[test]
+fn test_key_to_phase_increment() {
+    for sample_rate in [44100.0, 48000.0, 8000.0] {
+        for k in 0..=127 {
+            let f = key_to_frequency_raw(k);
+            let inc = key_to_phase_increment(k, sample_rate);
+            assert_eq!(inc, f / sample_rate);
+            assert!(f32::abs(inc * sample_rate - f) < 1.0e-6 * f);
+            let approx = key_to_phase_increment_approx(k, sample_rate);
+            assert!(f32::abs(inc - approx) < 0.001 * inc, "{} {}", inc, approx);
+            assert_eq!(inc > 0.5, f > sample_rate / 2.0);
+        }
+    }
+
+    // Every key is below Nyquist at 44.1 kHz and 48 kHz, but
+    // not at 8 kHz.
+    assert!(key_to_phase_increment(127, 44100.0) < 0.5);
+    assert!(key_to_phase_increment(127, 48000.0) < 0.5);
+    assert!(key_to_phase_increment(107, 8000.0) < 0.5);
+    assert!(key_to_phase_increment(108, 8000.0) > 0.5);
+}
+
+#[test]
+fn test_key_to_phase_increment_q32() {
+    for k in 0..=127 {
+        let inc = key_to_phase_increment_q32(k, 48000);
+        let f = inc as f64 * 48000.0 / (1u64 << 32) as f64;
+        let cents = 1200.0 * f64::log2(f / key_to_frequency_f64(k));
+        assert!(f64::abs(cents) < 0.5, "{} {} {}", k, inc, cents);
+    }
+
+    assert_eq!(key_to_phase_increment_q32(69, 1 << 31), 880);
+    assert_eq!(key_to_phase_increment_q32(57, 1 << 31), 440);
+    // 2^32 * 440 / 48000 is 39370533.55...
+    assert_eq!(key_to_phase_increment_q32(69, 48000), 39370534);
+
+    // Frequencies at or above the sample rate saturate.
+    assert_eq!(key_to_phase_increment_q32(127, 8000), u32::MAX);
+    assert_eq!(key_to_phase_increment_q32(69, 440), u32::MAX);
+    assert!(key_to_phase_increment_q32(69, 441) < u32::MAX);
+}
+
+#[test]
+fn test_key_to_phase_increment_fixed() {
+    for k in 0..=127 {
+        assert_eq!(
+            key_to_phase_increment_fixed(k, 48000.0, 32),
+            key_to_phase_increment_q32(k, 48000),
+        );
+        let inc = key_to_phase_increment_fixed_f64(k, 48000.0, 48);
+        let f = inc as f64 * 48000.0 / (1u64 << 48) as f64;
+        let cents = 1200.0 * f64::log2(f / key_to_frequency_f64(k));
+        assert!(f64::abs(cents) < 1.0e-6, "{} {} {}", k, inc, cents);
+    }
+
+    // 2^16 * 440 / 48000 is 600.74...
+    assert_eq!(key_to_phase_increment_fixed(69, 48000.0, 16), 601);
+    assert_eq!(key_to_phase_increment_fixed(69, 440.0, 16), 0xFFFF);
+    assert_eq!(key_to_phase_increment_fixed(69, 440.0, 0), 0);
+    assert_eq!(key_to_phase_increment_fixed_f64(69, 440.0, 64), u64::MAX);
+    assert_eq!(key_to_phase_increment_fixed_f64(69, 880.0, 64), 1 << 63);
+
+    // A 16-bit accumulator wraps once per cycle, 200 samples.
+    let inc = key_to_phase_increment_fixed(57, 44000.0, 16);
+    let mut phase: u32 = 0;
+    for _ in 0..200 {
+        phase = phase.wrapping_add(inc) & 0xFFFF;
+    }
+    assert_eq!(phase, 200 * inc - 0x10000);
+}
+
+#[test]
+#[should_panic(expected = "accumulator bits 33 out of range 0..=32")]
+fn test_key_to_phase_increment_fixed_too_wide() {
+    key_to_phase_increment_fixed(69, 48000.0, 33);
+}
+
+#[test]
+fn test_key_to_period_samples() {
+    let p = key_to_period_samples(69, 44100.0);
+    assert!(f32::abs(p - 100.227) < 0.001, "{}", p);
+    for k in 0..=127 {
+        let p = key_to_period_samples(k, 48000.0);
+        let a = key_to_period_samples_approx(k, 48000.0);
+        assert!(f32::abs(p - a) < 0.001 * p, "{} {} {}", k, p, a);
+    }
+}
+
+#[test]
+fn test_key_to_period_samples_rounded() {
+    for k in 0..=127 {
+        let (period, cents) = key_to_period_samples_rounded(k, 48000);
+        let exact = key_to_period_samples(k, 48000.0);
+        assert!(
+            f32::abs(period as f32 - exact) <= 0.5 + 1.0e-3,
+            "{} {}",
+            k,
+            period
+        );
+        let f = 48000.0 / period as f32;
+        let expected = 1200.0 * f32::log2(f / key_to_frequency_raw(k));
+        assert!(
+            f32::abs(cents - expected) < 0.01,
+            "{} {} {}",
+            k,
+            cents,
+            expected
+        );
+    }
+
+    // 44100 / 440 is 100.227, so 100 samples is sharp.
+    let (period, cents) = key_to_period_samples_rounded(69, 44100);
+    assert_eq!(period, 100);
+    assert!(cents > 0.0);
+    // 44100 / 261.63 is 168.56, so 169 samples is flat.
+    let (period, cents) = key_to_period_samples_rounded(60, 44100);
+    assert_eq!(period, 169);
+    assert!(cents < 0.0);
+
+    // A period shorter than half a sample clamps to one.
+    let (period, cents) = key_to_period_samples_rounded(127, 8000);
+    assert_eq!(period, 1);
+    assert!(f32::abs(cents - 1200.0 * f32::log2(8000.0 / key_to_frequency_raw(127))) < 0.01);
+    assert!(cents < -700.0);
+}
+
+#[test]
+fn test_key_to_angular_frequency() {
+    use core::f32::consts::PI;
+
+    assert_eq!(key_to_angular_frequency(69), 2.0 * PI * 440.0);
+    assert!(f32::abs(key_to_angular_frequency(69) - 2764.6) < 0.1);
+    for k in 0..=127 {
+        assert_eq!(
+            angular_frequency_to_key(key_to_angular_frequency(k)),
+            Some(k)
+        );
+        let w = key_to_angular_frequency_approx(k);
+        assert_eq!(angular_frequency_to_key(w), Some(k));
+    }
+    assert_eq!(angular_frequency_to_key(0.0), None);
+    assert_eq!(angular_frequency_to_key(f32::NAN), None);
+    assert_eq!(angular_frequency_to_key(1.0e9), None);
+    // 2π · 440 / 48000 = 0.0575958...
+    let w = key_to_normalized_angular_frequency(69, 48000.0);
+    assert!(f32::abs(w - 0.057_595_87) < 1.0e-7, "{}", w);
+    assert!(key_to_normalized_angular_frequency(107, 8000.0) < PI);
+    assert!(key_to_normalized_angular_frequency(108, 8000.0) > PI);
+
+    crate::test::check(
+        key_to_angular_frequency,
+        key_to_angular_frequency_approx,
+        0.001,
+    );
+    crate::test::check(
+        |k| key_to_normalized_angular_frequency(k, 48000.0),
+        |k| key_to_normalized_angular_frequency_approx(k, 48000.0),
+        0.001,
+    );
+}
@@ -0,0 +1,756 @@
+//! Scala scale (`.scl`) and keyboard mapping (`.kbm`)
+//! files.
+//!
+//! See the [Scala scale file
+//! format](https://www.huygens-fokker.org/scala/scl_format.html)
+//! and the [keyboard mapping
+//! format](https://www.huygens-fokker.org/scala/help.htm#mappings).
+
+use std::{string::String, vec::Vec};
+
+use crate::{TuningSystem, TuningTable, check_key, or_panic};
+
+/// Largest scale degree allowed in a keyboard mapping
+/// entry or formal octave, so that the degree of any key
+/// fits in an `i32`.
+const MAX_MAP_DEGREE: i32 = 1 << 16;
+
+/// Largest note count allowed in a scale file, or map size
+/// in a keyboard mapping, so that a bad count cannot ask for
+/// an enormous allocation.
+const MAX_SCALE_SIZE: usize = 1 << 16;
+
+/// Error returned when parsing a Scala scale or keyboard
+/// mapping file fails. Line numbers count from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalaError {
+    /// The file ends before the description line.
+    MissingDescription,
+    /// The note count is missing, zero, too large, or not a
+    /// number.
+    BadNoteCount {
+        /// The offending line, or the line past the end of
+        /// the file if the count is missing.
+        line: usize,
+    },
+    /// The number of pitch lines differs from the note count.
+    WrongDegreeCount {
+        /// The note count given in the file.
+        expected: usize,
+        /// The number of pitch lines found.
+        found: usize,
+    },
+    /// A ratio has a zero denominator.
+    ZeroDenominator {
+        /// The offending line.
+        line: usize,
+    },
+    /// A pitch in cents is negative.
+    NegativeCents {
+        /// The offending line.
+        line: usize,
+    },
+    /// A pitch is neither a number of cents nor a positive
+    /// ratio.
+    BadPitch {
+        /// The offending line.
+        line: usize,
+    },
+    /// A keyboard mapping header field is missing or
+    /// malformed, or the map size or formal octave is too
+    /// large.
+    BadField {
+        /// The offending line, or the line past the end of
+        /// the file if the field is missing.
+        line: usize,
+    },
+    /// A keyboard mapping header field names a midi key
+    /// outside the range `0..=127`.
+    KeyOutOfRange {
+        /// The offending line.
+        line: usize,
+    },
+    /// A keyboard mapping entry is neither a scale degree nor
+    /// `x`, or is too large.
+    BadMapEntry {
+        /// The offending line.
+        line: usize,
+    },
+    /// A keyboard mapping has more entries than its size.
+    TooManyMapEntries {
+        /// The map size given in the file.
+        expected: usize,
+        /// The number of entries found.
+        found: usize,
+    },
+    /// The reference key of a keyboard mapping is unmapped,
+    /// so no frequency can be given to the scale.
+    UnmappedReference,
+}
+
+impl core::fmt::Display for ScalaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ScalaError::MissingDescription => write!(f, "missing description line"),
+            ScalaError::BadNoteCount { line } => write!(f, "line {}: bad note count", line),
+            ScalaError::WrongDegreeCount { expected, found } => {
+                write!(f, "expected {} pitches, found {}", expected, found)
+            }
+            ScalaError::ZeroDenominator { line } => {
+                write!(f, "line {}: ratio with zero denominator", line)
+            }
+            ScalaError::NegativeCents { line } => write!(f, "line {}: negative cents", line),
+            ScalaError::BadPitch { line } => write!(f, "line {}: malformed pitch", line),
+            ScalaError::BadField { line } => write!(f, "line {}: bad mapping field", line),
+            ScalaError::KeyOutOfRange { line } => {
+                write!(f, "line {}: key out of range 0..=127", line)
+            }
+            ScalaError::BadMapEntry { line } => write!(f, "line {}: bad mapping entry", line),
+            ScalaError::TooManyMapEntries { expected, found } => {
+                write!(f, "expected {} mapping entries, found {}", expected, found)
+            }
+            ScalaError::UnmappedReference => write!(f, "reference key is unmapped"),
+        }
+    }
+}
+
+impl core::error::Error for ScalaError {}
+
+/// A scale read from a Scala `.scl` file: a description and
+/// a list of pitches above an implicit 1/1, the last of
+/// which is the period of the scale, usually 2/1. Pitches
+/// are kept in cents.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{ScalaScale, TuningSystem};
+/// let scl = "! fifths.scl\n!\nPure fifth and octave\n 2\n!\n 3/2\n 1200.0\n";
+/// let scale = ScalaScale::parse(scl).unwrap();
+/// assert_eq!(scale.description(), "Pure fifth and octave");
+/// assert_eq!(scale.cents()[1], 1200.0);
+/// let tuning = scale.to_tuning_table(60, 200.0);
+/// assert_eq!(tuning.key_to_frequency(61).round(), 300.0);
+/// assert_eq!(tuning.key_to_frequency(62), 400.0);
+/// assert_eq!(tuning.key_to_frequency(59).round(), 150.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalaScale {
+    description: String,
+    cents: Vec<f64>,
+}
+
+/// Parse a single pitch line as cents above 1/1. Anything
+/// after the first whitespace-separated field is ignored.
+fn parse_pitch(text: &str, line: usize) -> Result<f64, ScalaError> {
+    let pitch = text.split_whitespace().next().unwrap_or("");
+    let bad = ScalaError::BadPitch { line };
+    if pitch.contains('.') {
+        let cents: f64 = pitch.parse().map_err(|_| bad)?;
+        if !cents.is_finite() {
+            return Err(bad);
+        }
+        if cents < 0.0 {
+            return Err(ScalaError::NegativeCents { line });
+        }
+        return Ok(cents);
+    }
+    let (num, den) = pitch.split_once('/').unwrap_or((pitch, "1"));
+    let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !digits(num) || !digits(den) {
+        return Err(bad);
+    }
+    let num: f64 = num.parse().map_err(|_| bad)?;
+    let den: f64 = den.parse().map_err(|_| bad)?;
+    if den == 0.0 {
+        return Err(ScalaError::ZeroDenominator { line });
+    }
+    if num == 0.0 || !num.is_finite() || !den.is_finite() {
+        return Err(bad);
+    }
+    Ok(1200.0 * f64::log2(num / den))
+}
+
+/// Frequency `cents` above `root_freq`, computed in the
+/// same way as [key_to_frequency](crate::key_to_frequency).
+fn cents_frequency(root_freq: f32, cents: f64) -> f32 {
+    root_freq * f32::powf(2.0, cents as f32 / 1200.0)
+}
+
+impl ScalaScale {
+    /// Parses the text of a Scala `.scl` file. Lines
+    /// starting with `!` are comments. The first other line
+    /// is the description, which may be empty; the next is
+    /// the number of pitches, followed by one line for each
+    /// pitch. A pitch containing a `.` is in cents, and
+    /// must not be negative; otherwise it is a ratio such
+    /// as `3/2`, or a whole number such as `2`. Blank lines
+    /// after the pitches are ignored. There may be at most
+    /// 65536 pitches.
+    pub fn parse(text: &str) -> Result<Self, ScalaError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l))
+            .filter(|(_, l)| !l.starts_with('!'));
+
+        let (_, description) = lines.next().ok_or(ScalaError::MissingDescription)?;
+        let description = String::from(description.trim());
+
+        let end = text.lines().count() + 1;
+        let (line, count) = lines.next().unwrap_or((end, ""));
+        let count = count
+            .split_whitespace()
+            .next()
+            .and_then(|c| c.parse::<usize>().ok())
+            .filter(|&c| c > 0 && c <= MAX_SCALE_SIZE)
+            .ok_or(ScalaError::BadNoteCount { line })?;
+
+        let mut cents = Vec::with_capacity(count);
+        let mut found = 0;
+        for (line, text) in lines.filter(|(_, l)| !l.trim().is_empty()) {
+            found += 1;
+            if found <= count {
+                cents.push(parse_pitch(text, line)?);
+            }
+        }
+        if found != count {
+            return Err(ScalaError::WrongDegreeCount {
+                expected: count,
+                found,
+            });
+        }
+        Ok(ScalaScale { description, cents })
+    }
+
+    /// The description line of this scale.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The pitches of this scale in cents above 1/1, in file
+    /// order. The last is the period.
+    pub fn cents(&self) -> &[f64] {
+        &self.cents
+    }
+
+    /// The period of this scale in cents, usually 1200 for
+    /// an octave.
+    pub fn period_cents(&self) -> f64 {
+        self.cents[self.cents.len() - 1]
+    }
+
+    /// The pitch in cents above 1/1 of the given degree of
+    /// this scale, counting on through further periods:
+    /// degree 0 is 1/1, and degree [cents](Self::cents)`.len()`
+    /// is the period.
+    pub fn degree_cents(&self, degree: i32) -> f64 {
+        let n = self.cents.len() as i32;
+        let (periods, step) = (degree.div_euclid(n), degree.rem_euclid(n));
+        let cents = match step {
+            0 => 0.0,
+            s => self.cents[s as usize - 1],
+        };
+        cents + periods as f64 * self.period_cents()
+    }
+
+    /// Realizes this scale as a tuning with consecutive midi
+    /// keys on consecutive scale degrees, and `root_key` at
+    /// `root_freq` Hz as degree 0. Frequencies are computed
+    /// from cents as [key_to_frequency](crate::key_to_frequency)
+    /// computes them from keys, so that the 12-TET scale
+    /// rooted at A4 = 440 Hz reproduces it exactly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root_key` is not in the range `0..=127`, or
+    /// if `root_freq` is not a finite positive number.
+    pub fn to_tuning_table(&self, root_key: u8, root_freq: f32) -> TuningTable {
+        let root_key = or_panic(check_key(root_key));
+        assert!(
+            root_freq > 0.0 && root_freq.is_finite(),
+            "root frequency must be finite and positive",
+        );
+        let mut frequencies = [0.0; 128];
+        for (k, f) in frequencies.iter_mut().enumerate() {
+            *f = cents_frequency(root_freq, self.degree_cents(k as i32 - root_key as i32));
+        }
+        TuningTable::new(frequencies)
+    }
+
+    /// Realizes this scale on the keys of a keyboard mapping,
+    /// giving the frequency in Hz of each mapped key and
+    /// `None` for each unmapped one. Frequencies are computed
+    /// as for [to_tuning_table](Self::to_tuning_table), so
+    /// the 12-TET scale with the standard mapping reproduces
+    /// [key_to_frequency](crate::key_to_frequency) exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use keytones::{KeyboardMapping, ScalaScale};
+    /// let scale = ScalaScale::parse("\n2\n3/2\n2/1\n").unwrap();
+    /// let kbm = "3\n60\n65\n60\n60\n100.0\n2\n0\nx\n1\n";
+    /// let mapping = KeyboardMapping::parse(kbm).unwrap();
+    /// let freqs = scale.mapped_frequencies(&mapping);
+    /// assert_eq!(freqs[59], None);
+    /// assert_eq!(freqs[60], Some(100.0));
+    /// assert_eq!(freqs[61], None);
+    /// assert_eq!(freqs[62].map(f32::round), Some(150.0));
+    /// assert_eq!(freqs[63], Some(200.0));
+    /// ```
+    pub fn mapped_frequencies(&self, mapping: &KeyboardMapping) -> [Option<f32>; 128] {
+        let (reference_key, reference_hz) = mapping.reference();
+        let reference = mapping
+            .mapped_degree(reference_key)
+            .map(|d| self.degree_cents(d));
+        // Parsing the mapping checks that the reference key is
+        // mapped.
+        let reference = reference.expect("reference key is unmapped");
+        core::array::from_fn(|k| {
+            let cents = self.degree_cents(mapping.degree(k as u8)?);
+            Some(cents_frequency(reference_hz, cents - reference))
+        })
+    }
+
+    /// Realizes this scale on the keys of a keyboard mapping
+    /// as [mapped_frequencies](Self::mapped_frequencies) does,
+    /// taking the frequencies of unmapped keys from the
+    /// `unmapped` tuning.
+    pub fn mapped_tuning_table(
+        &self,
+        mapping: &KeyboardMapping,
+        unmapped: &impl TuningSystem,
+    ) -> TuningTable {
+        let mapped = self.mapped_frequencies(mapping);
+        let frequencies = core::array::from_fn(|k| {
+            mapped[k].unwrap_or_else(|| unmapped.key_to_frequency(k as u8))
+        });
+        TuningTable::new(frequencies)
+    }
+}
+
+/// A keyboard mapping read from a Scala `.kbm` file, saying
+/// which scale degree each midi key plays and which key is
+/// tuned to a reference frequency.
+///
+/// The mapping is a list of entries repeating every
+/// [size](Self::size) keys, starting at the middle key. Each
+/// entry is a scale degree, or `None` for a key that is not
+/// mapped. Each repetition of the list moves up by the
+/// degree of the formal octave, which need not equal the
+/// size: a 7-note scale may be mapped onto the white keys
+/// with a size of 12 and a formal octave of 7. A size of 0
+/// maps consecutive keys to consecutive degrees. Only keys
+/// from the first to the last key are mapped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyboardMapping {
+    first_key: u8,
+    last_key: u8,
+    middle_key: u8,
+    reference_key: u8,
+    reference_hz: f32,
+    octave_degree: i32,
+    size: usize,
+    entries: Vec<Option<i32>>,
+}
+
+impl KeyboardMapping {
+    /// Parses the text of a Scala `.kbm` file. Lines
+    /// starting with `!`, and blank lines, are ignored. The
+    /// remaining lines hold, in order, the map size, the
+    /// first and last keys to map, the middle key, the
+    /// reference key and its frequency in Hz, the scale
+    /// degree of the formal octave, and then up to map size
+    /// entries, each a scale degree or `x` for an unmapped
+    /// key. Missing entries at the end of the list are
+    /// unmapped. The map size and scale degrees, including
+    /// that of the formal octave, may be at most 65536.
+    pub fn parse(text: &str) -> Result<Self, ScalaError> {
+        let end = text.lines().count() + 1;
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !l.is_empty() && !l.starts_with('!'))
+            .map(|(line, l)| (line, l.split_whitespace().next().unwrap_or(l)));
+
+        let mut field = || lines.next().ok_or(ScalaError::BadField { line: end });
+        let number = |(line, text): (usize, &str)| {
+            text.parse::<i32>()
+                .ok()
+                .filter(|&n| n >= 0)
+                .ok_or(ScalaError::BadField { line })
+        };
+        let key = |(line, text)| {
+            let n = number((line, text))?;
+            u8::try_from(n)
+                .ok()
+                .filter(|&k| k <= 127)
+                .ok_or(ScalaError::KeyOutOfRange { line })
+        };
+
+        let (line, text) = field()?;
+        let size = number((line, text))? as usize;
+        if size > MAX_SCALE_SIZE {
+            return Err(ScalaError::BadField { line });
+        }
+        let first_key = key(field()?)?;
+        let last_key = key(field()?)?;
+        let middle_key = key(field()?)?;
+        let reference_key = key(field()?)?;
+        let (line, hz) = field()?;
+        let reference_hz = hz
+            .parse::<f32>()
+            .ok()
+            .filter(|&f| f > 0.0 && f.is_finite())
+            .ok_or(ScalaError::BadField { line })?;
+        let (line, text) = field()?;
+        let octave_degree = number((line, text))?;
+        if octave_degree > MAX_MAP_DEGREE {
+            return Err(ScalaError::BadField { line });
+        }
+
+        let mut entries = Vec::with_capacity(size);
+        for (line, text) in lines {
+            let entry = match text {
+                "x" | "X" => None,
+                _ => Some(
+                    number((line, text))
+                        .ok()
+                        .filter(|&n| n <= MAX_MAP_DEGREE)
+                        .ok_or(ScalaError::BadMapEntry { line })?,
+                ),
+            };
+            entries.push(entry);
+        }
+        if entries.len() > size {
+            return Err(ScalaError::TooManyMapEntries {
+                expected: size,
+                found: entries.len(),
+            });
+        }
+        entries.resize(size, None);
+
+        let mapping = KeyboardMapping {
+            first_key,
+            last_key,
+            middle_key,
+            reference_key,
+            reference_hz,
+            octave_degree,
+            size,
+            entries,
+        };
+        if mapping.mapped_degree(reference_key).is_none() {
+            return Err(ScalaError::UnmappedReference);
+        }
+        Ok(mapping)
+    }
+
+    /// The number of keys after which the mapping repeats,
+    /// or 0 for a linear mapping.
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The lowest and highest keys that are mapped.
+    pub const fn key_range(&self) -> (u8, u8) {
+        (self.first_key, self.last_key)
+    }
+
+    /// The key played by the first entry of the mapping.
+    pub const fn middle_key(&self) -> u8 {
+        self.middle_key
+    }
+
+    /// The reference key and its frequency in Hz.
+    pub const fn reference(&self) -> (u8, f32) {
+        (self.reference_key, self.reference_hz)
+    }
+
+    /// The scale degree by which each repetition of the
+    /// mapping moves up.
+    pub const fn octave_degree(&self) -> i32 {
+        self.octave_degree
+    }
+
+    /// The entries of the mapping, one for each of
+    /// [size](Self::size) keys starting at the middle key.
+    pub fn entries(&self) -> &[Option<i32>] {
+        &self.entries
+    }
+
+    /// Scale degree played by the given key, ignoring the
+    /// range of mapped keys.
+    fn mapped_degree(&self, key: u8) -> Option<i32> {
+        let offset = key as i32 - self.middle_key as i32;
+        if self.size == 0 {
+            return Some(offset);
+        }
+        let size = self.size as i32;
+        let entry = self.entries[offset.rem_euclid(size) as usize]?;
+        Some(entry + offset.div_euclid(size) * self.octave_degree)
+    }
+
+    /// The scale degree played by the given midi key, or
+    /// `None` if the key is not mapped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in the range `0..=127`.
+    pub fn degree(&self, key: u8) -> Option<i32> {
+        let key = or_panic(check_key(key));
+        if !(self.first_key..=self.last_key).contains(&key) {
+            return None;
+        }
+        self.mapped_degree(key)
+    }
+}
+
+#[test]
+fn test_scala_fixtures() {
+    use crate::{Meantone, TuningSystem, key_to_frequency_raw};
+
+    let tet = ScalaScale::parse(include_str!("../fixtures/12-tet.scl")).unwrap();
+    assert_eq!(tet.description(), "12 tone equal temperament");
+    assert_eq!(tet.cents().len(), 12);
+    assert_eq!(tet.period_cents(), 1200.0);
+    let tuning = tet.to_tuning_table(69, 440.0);
+    for k in 0..=127 {
+        assert_eq!(tuning.key_to_frequency(k), key_to_frequency_raw(k), "{}", k);
+    }
+
+    let meantone = ScalaScale::parse(include_str!("../fixtures/meanquar.scl")).unwrap();
+    assert!(meantone.description().starts_with("1/4-comma meantone"));
+    assert_eq!(meantone.cents()[3], 1200.0 * f64::log2(1.25));
+    assert_eq!(meantone.cents()[7], 2.0 * meantone.cents()[3]);
+    let tuning = meantone.to_tuning_table(60, key_to_frequency_raw(60));
+    let expected = Meantone::quarter_comma(60);
+    for k in 0..=127 {
+        let (f, g) = (tuning.key_to_frequency(k), expected.frequency(k));
+        assert!(f32::abs(f - g) < 1.0e-6 * g, "{} {} {}", k, f, g);
+    }
+
+    let ji = ScalaScale::parse(include_str!("../fixtures/ji-12.scl")).unwrap();
+    let tuning = ji.to_tuning_table(62, key_to_frequency_raw(62));
+    let expected = crate::JustIntonation::new(62);
+    for k in 0..=127 {
+        let (f, g) = (tuning.key_to_frequency(k), expected.frequency(k));
+        assert!(f32::abs(f - g) < 1.0e-6 * g, "{} {} {}", k, f, g);
+    }
+    assert_eq!(ji.degree_cents(-1), ji.cents()[10] - 1200.0);
+    assert_eq!(ji.degree_cents(24), 2400.0);
+}
+
+#[test]
+fn test_scala_parse() {
+    let scale = ScalaScale::parse("\n1\n700.0 fifth\n\n").unwrap();
+    assert_eq!(scale.description(), "");
+    assert_eq!(scale.cents(), &[700.0]);
+    assert_eq!(scale.degree_cents(-2), -1400.0);
+
+    let tests: &[(&str, ScalaError)] = &[
+        ("", ScalaError::MissingDescription),
+        ("! only a comment\n", ScalaError::MissingDescription),
+        ("desc\n", ScalaError::BadNoteCount { line: 2 }),
+        ("desc\nmany\n3/2\n", ScalaError::BadNoteCount { line: 2 }),
+        ("desc\n0\n", ScalaError::BadNoteCount { line: 2 }),
+        (
+            "desc\n65536\n",
+            ScalaError::WrongDegreeCount {
+                expected: 65536,
+                found: 0,
+            },
+        ),
+        ("desc\n65537\n", ScalaError::BadNoteCount { line: 2 }),
+        (
+            "desc\n9999999999999999999\n",
+            ScalaError::BadNoteCount { line: 2 },
+        ),
+        (
+            "desc\n2\n3/2\n",
+            ScalaError::WrongDegreeCount {
+                expected: 2,
+                found: 1,
+            },
+        ),
+        (
+            "desc\n1\n3/2\n2/1\n",
+            ScalaError::WrongDegreeCount {
+                expected: 1,
+                found: 2,
+            },
+        ),
+        (
+            "desc\n2\n!\n3/0\n2/1\n",
+            ScalaError::ZeroDenominator { line: 4 },
+        ),
+        ("desc\n1\n-100.0\n", ScalaError::NegativeCents { line: 3 }),
+        ("desc\n1\n0/1\n", ScalaError::BadPitch { line: 3 }),
+        ("desc\n1\n-3/2\n", ScalaError::BadPitch { line: 3 }),
+        ("desc\n1\n3/\n", ScalaError::BadPitch { line: 3 }),
+        ("desc\n1\nfifth\n", ScalaError::BadPitch { line: 3 }),
+        ("desc\n1\ninf.\n", ScalaError::BadPitch { line: 3 }),
+    ];
+    for (text, err) in tests {
+        assert_eq!(ScalaScale::parse(text), Err(*err), "{:?}", text);
+    }
+}
+
+#[test]
+#[should_panic(expected = "root frequency must be finite and positive")]
+fn test_scala_bad_root_frequency() {
+    ScalaScale::parse("\n1\n2/1\n")
+        .unwrap()
+        .to_tuning_table(60, 0.0);
+}
+
+#[test]
+fn test_keyboard_mapping_fixtures() {
+    use crate::{EqualTemperament, key_to_frequency_raw};
+
+    let tet = ScalaScale::parse(include_str!("../fixtures/12-tet.scl")).unwrap();
+    let standard = KeyboardMapping::parse(include_str!("../fixtures/12-tet.kbm")).unwrap();
+    assert_eq!(standard.size(), 12);
+    assert_eq!(standard.key_range(), (0, 127));
+    assert_eq!(standard.middle_key(), 60);
+    assert_eq!(standard.reference(), (69, 440.0));
+    assert_eq!(standard.octave_degree(), 12);
+    let freqs = tet.mapped_frequencies(&standard);
+    for k in 0..=127 {
+        assert_eq!(freqs[k as usize], Some(key_to_frequency_raw(k)), "{}", k);
+    }
+
+    // A 7-note scale on the white keys, with the black keys
+    // and the keys off an 88-key piano unmapped.
+    let diatonic = "! diatonic\nJust major\n7\n9/8\n5/4\n4/3\n3/2\n5/3\n15/8\n2/1\n";
+    let diatonic = ScalaScale::parse(diatonic).unwrap();
+    let white = KeyboardMapping::parse(include_str!("../fixtures/white-keys.kbm")).unwrap();
+    assert_eq!(white.entries()[..3], [Some(0), None, Some(1)]);
+    let freqs = diatonic.mapped_frequencies(&white);
+    let (_, c4) = white.reference();
+    for k in 0..=127 {
+        let mapped = (21..=108).contains(&k) && crate::is_white_key(k);
+        assert_eq!(freqs[k as usize].is_some(), mapped, "{}", k);
+    }
+    let ratio = |k: usize| freqs[k].unwrap() / c4;
+    assert_eq!(freqs[60], Some(c4));
+    assert!(f32::abs(ratio(62) - 9.0 / 8.0) < 1.0e-6);
+    assert!(f32::abs(ratio(67) - 3.0 / 2.0) < 1.0e-6);
+    assert!(f32::abs(ratio(72) - 2.0) < 1.0e-6);
+    assert!(f32::abs(ratio(59) - 15.0 / 16.0) < 1.0e-6);
+    assert!(f32::abs(ratio(21) - 5.0 / 3.0 / 16.0) < 1.0e-6);
+
+    // Twelve keys of each octave on 19-EDO, smaller than its
+    // formal octave of 19 degrees.
+    let edo19 = ScalaScale::parse(include_str!("../fixtures/19-edo.scl")).unwrap();
+    let mapping = KeyboardMapping::parse(include_str!("../fixtures/19-edo-12.kbm")).unwrap();
+    assert_eq!(mapping.degree(60), Some(0));
+    assert_eq!(mapping.degree(72), Some(19));
+    assert_eq!(mapping.degree(59), Some(-2));
+    assert_eq!(mapping.degree(69), Some(14));
+    let freqs = edo19.mapped_frequencies(&mapping);
+    assert_eq!(freqs[69], Some(440.0));
+    for k in 0..=127 {
+        let degree = mapping.degree(k).unwrap() - 14;
+        let expected = 440.0 * f64::powf(2.0, degree as f64 / 19.0);
+        let f = freqs[k as usize].unwrap() as f64;
+        assert!(f64::abs(f - expected) < 1.0e-5 * expected, "{} {}", k, f);
+    }
+
+    let table = edo19.mapped_tuning_table(&white, &EqualTemperament);
+    let freqs = edo19.mapped_frequencies(&white);
+    for k in 0..=127 {
+        let expected = freqs[k as usize].unwrap_or(key_to_frequency_raw(k));
+        assert_eq!(table.key_to_frequency(k), expected);
+    }
+}
+
+#[test]
+fn test_keyboard_mapping_parse() {
+    // Missing entries at the end are unmapped.
+    let mapping = KeyboardMapping::parse("12\n0\n127\n60\n60\n440.0\n12\n0\n2\n").unwrap();
+    assert_eq!(mapping.entries().len(), 12);
+    assert_eq!(mapping.degree(60), Some(0));
+    assert_eq!(mapping.degree(61), Some(2));
+    assert_eq!(mapping.degree(62), None);
+    assert_eq!(mapping.degree(71), None);
+    assert_eq!(mapping.degree(72), Some(12));
+
+    // A size of zero maps keys linearly.
+    let linear = KeyboardMapping::parse("0\n10\n20\n15\n15\n100.0 Hz\n0\n").unwrap();
+    assert_eq!(linear.degree(9), None);
+    assert_eq!(linear.degree(10), Some(-5));
+    assert_eq!(linear.degree(20), Some(5));
+    assert_eq!(linear.degree(21), None);
+
+    // The largest map size allowed.
+    let wide = KeyboardMapping::parse("65536\n0\n127\n60\n60\n440\n12\n0\n").unwrap();
+    assert_eq!(wide.entries().len(), 65536);
+    assert_eq!(wide.degree(61), None);
+
+    // The largest degrees allowed cannot overflow.
+    let big = KeyboardMapping::parse("1\n0\n127\n127\n127\n440\n65536\n65536\n").unwrap();
+    assert_eq!(big.degree(0), Some(65536 - 127 * 65536));
+    assert_eq!(big.degree(127), Some(65536));
+
+    let tests: &[(&str, ScalaError)] = &[
+        ("", ScalaError::BadField { line: 1 }),
+        ("12\n0\n127\n", ScalaError::BadField { line: 4 }),
+        (
+            "-1\n0\n127\n60\n69\n440\n12\n",
+            ScalaError::BadField { line: 1 },
+        ),
+        (
+            "12\n0\n128\n60\n69\n440\n12\n",
+            ScalaError::KeyOutOfRange { line: 3 },
+        ),
+        (
+            "12\n0\n127\n60\n69\n0.0\n12\n",
+            ScalaError::BadField { line: 6 },
+        ),
+        (
+            "12\n0\n127\n60\n69\nfast\n12\n",
+            ScalaError::BadField { line: 6 },
+        ),
+        (
+            "1\n0\n127\n60\n60\n440\n12\n0\nx\n",
+            ScalaError::TooManyMapEntries {
+                expected: 1,
+                found: 2,
+            },
+        ),
+        (
+            "2\n0\n127\n60\n60\n440\n12\n0\ny\n",
+            ScalaError::BadMapEntry { line: 9 },
+        ),
+        (
+            "2147483647\n0\n127\n60\n60\n440\n12\n0\n",
+            ScalaError::BadField { line: 1 },
+        ),
+        (
+            "65537\n0\n127\n60\n60\n440\n12\n0\n",
+            ScalaError::BadField { line: 1 },
+        ),
+        (
+            "12\n0\n127\n60\n60\n440\n2147483647\n0\n",
+            ScalaError::BadField { line: 7 },
+        ),
+        (
+            "2\n0\n127\n60\n60\n440\n2\n0\n2147483647\n",
+            ScalaError::BadMapEntry { line: 9 },
+        ),
+        (
+            "2\n0\n127\n60\n61\n440\n12\n0\nx\n",
+            ScalaError::UnmappedReference,
+        ),
+        (
+            "2\n0\n127\n60\n61\n440\n12\n0\n",
+            ScalaError::UnmappedReference,
+        ),
+    ];
+    for (text, err) in tests {
+        assert_eq!(KeyboardMapping::parse(text), Err(*err), "{:?}", text);
+    }
+}
@@ -0,0 +1,154 @@
+//! Keys and frequencies of musical scales.
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(feature = "std")]
+use crate::key_to_frequency_raw;
+use crate::{check_key, or_panic};
+
+/// Intervals in semitones of the major (Ionian) scale.
+pub const MAJOR_SCALE: [u8; 7] = [2, 2, 1, 2, 2, 2, 1];
+/// Intervals in semitones of the natural minor (Aeolian)
+/// scale.
+pub const MINOR_SCALE: [u8; 7] = [2, 1, 2, 2, 1, 2, 2];
+/// Intervals in semitones of the harmonic minor scale.
+pub const HARMONIC_MINOR_SCALE: [u8; 7] = [2, 1, 2, 2, 1, 3, 1];
+/// Intervals in semitones of the ascending melodic minor
+/// scale.
+pub const MELODIC_MINOR_SCALE: [u8; 7] = [2, 1, 2, 2, 2, 2, 1];
+/// Intervals in semitones of the major pentatonic scale.
+pub const MAJOR_PENTATONIC_SCALE: [u8; 5] = [2, 2, 3, 2, 3];
+/// Intervals in semitones of the minor pentatonic scale.
+pub const MINOR_PENTATONIC_SCALE: [u8; 5] = [3, 2, 2, 3, 2];
+/// Intervals in semitones of the six-note blues scale.
+pub const BLUES_SCALE: [u8; 6] = [3, 2, 1, 1, 3, 2];
+/// Intervals in semitones of the chromatic scale.
+pub const CHROMATIC_SCALE: [u8; 12] = [1; 12];
+
+/// An iterator over the keys of a scale, from the root up to
+/// key 127.
+fn scale_iter(root: u8, pattern: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    let root = or_panic(check_key(root));
+    assert!(
+        !pattern.is_empty() && !pattern.contains(&0),
+        "scale pattern must be nonempty with positive intervals",
+    );
+    let mut steps = pattern.iter().cycle();
+    let mut next = Some(root);
+    core::iter::from_fn(move || {
+        let key = next.filter(|&k| k <= 127)?;
+        next = steps.next().and_then(|&step| key.checked_add(step));
+        Some(key)
+    })
+}
+
+/// Fills `out` with the midi keys of the scale starting at
+/// `root` and stepping upward through the semitone intervals
+/// of `pattern`, repeating the pattern as needed, up to key
+/// 127. Returns the number of keys written, which is limited
+/// by the length of `out`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{scale_keys, MAJOR_SCALE};
+/// let mut out = [0; 8];
+/// assert_eq!(scale_keys(60, &MAJOR_SCALE, &mut out), 8);
+/// assert_eq!(out, [60, 62, 64, 65, 67, 69, 71, 72]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `root` is not in the range `0..=127`, or if
+/// `pattern` is empty or contains a zero interval.
+pub fn scale_keys(root: u8, pattern: &[u8], out: &mut [u8]) -> usize {
+    let mut n = 0;
+    for (k, o) in scale_iter(root, pattern).zip(out.iter_mut()) {
+        *o = k;
+        n += 1;
+    }
+    n
+}
+
+/// Returns the frequencies of all the keys of the scale
+/// given by `root` and `pattern`, as found by [scale_keys].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{scale_frequencies, MAJOR_PENTATONIC_SCALE};
+/// let freqs = scale_frequencies(117, &MAJOR_PENTATONIC_SCALE);
+/// assert_eq!(freqs.len(), 5);
+/// assert_eq!(freqs[0], 7040.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `root` is not in the range `0..=127`, or if
+/// `pattern` is empty or contains a zero interval.
+#[cfg(feature = "std")]
+pub fn scale_frequencies(root: u8, pattern: &[u8]) -> Vec<f32> {
+    scale_iter(root, pattern)
+        .map(key_to_frequency_raw)
+        .collect()
+}
+
+#[test]
+fn test_scale_keys() {
+    let mut out = [0; 128];
+    let n = scale_keys(0, &CHROMATIC_SCALE, &mut out);
+    assert_eq!(n, 128);
+    assert!(out.iter().enumerate().all(|(i, &k)| i == k as usize));
+
+    let n = scale_keys(60, &MAJOR_SCALE, &mut out);
+    // C4 up to C9, then D9 through G9.
+    assert_eq!(n, 5 * 7 + 1 + 4);
+    assert_eq!(out[n - 1], 127);
+    for &k in &out[..n] {
+        assert!(!crate::is_black_key(k), "{}", k);
+    }
+
+    let n = scale_keys(57, &MINOR_SCALE, &mut out);
+    assert_eq!(&out[..8], &[57, 59, 60, 62, 64, 65, 67, 69]);
+    assert_eq!(out[n - 1], 127);
+
+    let mut short = [0; 3];
+    assert_eq!(scale_keys(60, &BLUES_SCALE, &mut short), 3);
+    assert_eq!(short, [60, 63, 65]);
+
+    assert_eq!(scale_keys(127, &[255], &mut out), 1);
+    assert_eq!(out[0], 127);
+
+    for pattern in [
+        &MAJOR_SCALE[..],
+        &MINOR_SCALE,
+        &HARMONIC_MINOR_SCALE,
+        &MELODIC_MINOR_SCALE,
+        &MAJOR_PENTATONIC_SCALE,
+        &MINOR_PENTATONIC_SCALE,
+        &BLUES_SCALE,
+        &CHROMATIC_SCALE,
+    ] {
+        assert_eq!(pattern.iter().sum::<u8>(), 12);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_scale_frequencies() {
+    let freqs = scale_frequencies(69, &MAJOR_SCALE);
+    let mut keys = [0; 128];
+    let n = scale_keys(69, &MAJOR_SCALE, &mut keys);
+    assert_eq!(freqs.len(), n);
+    for (f, &k) in freqs.iter().zip(&keys[..n]) {
+        assert_eq!(*f, key_to_frequency_raw(k));
+    }
+    assert_eq!(freqs[7], 880.0);
+}
+
+#[test]
+#[should_panic(expected = "scale pattern must be nonempty with positive intervals")]
+fn test_scale_keys_bad_pattern() {
+    scale_keys(60, &[2, 0], &mut [0; 4]);
+}
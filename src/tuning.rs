@@ -0,0 +1,1662 @@
+//! Tuning systems mapping midi keys to frequencies.
+
+#[cfg(all(not(feature = "std"), not(test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::{
+    check_key, key_to_frequency_approx_raw, key_to_frequency_raw, key_to_period_approx_raw,
+    key_to_period_raw, or_panic, try_key_to_params_bottom,
+};
+
+/// A tuning system: an assignment of a frequency in Hz to
+/// each midi key.
+pub trait TuningSystem {
+    /// Frequency in Hz of the given midi key in this tuning.
+    ///
+    /// # Panics
+    ///
+    /// Implementations should panic if `key` is not in the
+    /// range `0..=127`.
+    fn key_to_frequency(&self, key: u8) -> f32;
+}
+
+/// Standard twelve-tone equal temperament with A4 = 440 Hz,
+/// as computed by [key_to_frequency_raw].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{EqualTemperament, TuningSystem};
+/// assert_eq!(EqualTemperament.key_to_frequency(69), 440.0);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EqualTemperament;
+
+impl TuningSystem for EqualTemperament {
+    fn key_to_frequency(&self, key: u8) -> f32 {
+        key_to_frequency_raw(key)
+    }
+}
+
+/// A tuning given by an explicit table of frequencies in Hz,
+/// indexed by midi key. This allows arbitrary per-key
+/// tuning, such as that set by MIDI Tuning Standard
+/// messages.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{TuningSystem, TuningTable, FREQUENCY_TABLE};
+/// let mut table = FREQUENCY_TABLE;
+/// table[69] = 442.0;
+/// let tuning = TuningTable::new(table);
+/// assert_eq!(tuning.key_to_frequency(69), 442.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TuningTable {
+    frequencies: [f32; 128],
+}
+
+impl TuningTable {
+    /// Make a tuning from a table of frequencies in Hz.
+    pub const fn new(frequencies: [f32; 128]) -> Self {
+        TuningTable { frequencies }
+    }
+
+    /// The table of frequencies in Hz for this tuning.
+    pub const fn frequencies(&self) -> &[f32; 128] {
+        &self.frequencies
+    }
+}
+
+impl TuningSystem for TuningTable {
+    fn key_to_frequency(&self, key: u8) -> f32 {
+        self.frequencies[or_panic(check_key(key)) as usize]
+    }
+}
+
+/// Classic 5-limit just intonation ratios used by
+/// [JustIntonation], as `(numerator, denominator)` pairs in
+/// lowest terms for each of the twelve chromatic degrees
+/// above the root.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::JUST_RATIOS;
+/// let (num, den) = JUST_RATIOS[4];
+/// assert_eq!(format!("{}:{}", num, den), "5:4");
+/// ```
+pub const JUST_RATIOS: [(u32, u32); 12] = [
+    (1, 1),
+    (16, 15),
+    (9, 8),
+    (6, 5),
+    (5, 4),
+    (4, 3),
+    (45, 32),
+    (3, 2),
+    (8, 5),
+    (5, 3),
+    (9, 5),
+    (15, 8),
+];
+
+/// A 5-limit just intonation tuning built on a root key. The
+/// root keeps its equal-tempered frequency, and each other
+/// key is tuned by a small-integer ratio from the root,
+/// shifted by octaves as needed.
+///
+/// | Degree | Ratio | Deviation from 12-TET |
+/// |-------:|------:|----------------------:|
+/// |      0 |   1/1 |                  0.00¢ |
+/// |      1 | 16/15 |                +11.73¢ |
+/// |      2 |   9/8 |                 +3.91¢ |
+/// |      3 |   6/5 |                +15.64¢ |
+/// |      4 |   5/4 |                -13.69¢ |
+/// |      5 |   4/3 |                 -1.96¢ |
+/// |      6 | 45/32 |                 -9.78¢ |
+/// |      7 |   3/2 |                 +1.96¢ |
+/// |      8 |   8/5 |                +13.69¢ |
+/// |      9 |   5/3 |                -15.64¢ |
+/// |     10 |   9/5 |                +17.60¢ |
+/// |     11 |  15/8 |                -11.73¢ |
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{JustIntonation, TuningSystem};
+/// let tuning = JustIntonation::new(60);
+/// let c = tuning.key_to_frequency(60);
+/// assert_eq!(tuning.key_to_frequency(67), 1.5 * c);
+/// assert_eq!(tuning.frequency(64), 1.25 * c);
+/// assert_eq!(tuning.ratio(64), (5, 4));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JustIntonation {
+    root: u8,
+}
+
+impl JustIntonation {
+    /// Make a just intonation tuning on the given root key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root` is not in the range `0..=127`.
+    pub fn new(root: u8) -> Self {
+        let root = or_panic(check_key(root));
+        JustIntonation { root }
+    }
+
+    /// The root key of this tuning.
+    pub const fn root(&self) -> u8 {
+        self.root
+    }
+
+    /// Ratio from [JUST_RATIOS] of the given midi key to the
+    /// root, ignoring octaves.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in the range `0..=127`.
+    pub fn ratio(&self, key: u8) -> (u32, u32) {
+        let interval = or_panic(check_key(key)) as i32 - self.root as i32;
+        JUST_RATIOS[interval.rem_euclid(12) as usize]
+    }
+
+    /// Computes the frequency for a given midi key in this
+    /// tuning.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in the range `0..=127`.
+    pub fn frequency(&self, key: u8) -> f32 {
+        ratio_frequency(self.root, key, |degree| rational(JUST_RATIOS[degree]))
+    }
+}
+
+impl TuningSystem for JustIntonation {
+    fn key_to_frequency(&self, key: u8) -> f32 {
+        self.frequency(key)
+    }
+}
+
+/// Frequency of `key` in a tuning given by `ratio` of each
+/// chromatic degree `0..12` above an equal-tempered `root`,
+/// shifted by octaves as needed.
+fn ratio_frequency(root: u8, key: u8, ratio: impl Fn(usize) -> f64) -> f32 {
+    let interval = or_panic(check_key(key)) as i32 - root as i32;
+    let ratio = ratio(interval.rem_euclid(12) as usize);
+    let octave = f64::powi(2.0, interval.div_euclid(12));
+    (key_to_frequency_raw(root) as f64 * ratio * octave) as f32
+}
+
+/// Value of a `(numerator, denominator)` ratio.
+fn rational((num, den): (u32, u32)) -> f64 {
+    num as f64 / den as f64
+}
+
+/// Deviation in cents from twelve-tone equal temperament of
+/// a chromatic degree tuned to `ratio` above its root.
+fn et_deviation_cents(ratio: f64, degree: u8) -> f32 {
+    (1200.0 * f64::log2(ratio) - 100.0 * degree as f64) as f32
+}
+
+/// Pythagorean ratios for each chromatic degree above the
+/// root, found by stacking eleven perfect fifths upward
+/// from the root and reducing each into the root's octave.
+const PYTHAGOREAN_RATIOS: [(u32, u32); 12] = {
+    let mut ratios = [(1, 1); 12];
+    let (mut num, mut den) = (1, 1);
+    let mut i = 1;
+    while i < 12 {
+        num *= 3;
+        den *= 2;
+        if num >= 2 * den {
+            den *= 2;
+        }
+        ratios[7 * i % 12] = (num, den);
+        i += 1;
+    }
+    ratios
+};
+
+/// A Pythagorean tuning built on a root key. The root keeps
+/// its equal-tempered frequency, and the other eleven pitch
+/// classes are found by stacking pure 3/2 fifths upward from
+/// the root, reduced into a single octave. Other octaves
+/// are shifted by factors of two.
+///
+/// Eleven of the twelve fifths are pure. The remaining
+/// "wolf" fifth, from the last stacked note back to the
+/// root, is flat by a Pythagorean comma (about 23.46¢); see
+/// [PythagoreanTuning::wolf_interval].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{PythagoreanTuning, TuningSystem};
+/// let tuning = PythagoreanTuning::new(60);
+/// let c = tuning.key_to_frequency(60);
+/// assert_eq!(tuning.key_to_frequency(62), 9.0 / 8.0 * c);
+/// assert_eq!(tuning.wolf_interval(), (5, 0));
+/// assert_eq!(tuning.deviation_from_et_cents(4).round(), 8.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PythagoreanTuning {
+    root: u8,
+}
+
+impl PythagoreanTuning {
+    /// Make a Pythagorean tuning on the given root key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root` is not in the range `0..=127`.
+    pub fn new(root: u8) -> Self {
+        let root = or_panic(check_key(root));
+        PythagoreanTuning { root }
+    }
+
+    /// The root key of this tuning.
+    pub const fn root(&self) -> u8 {
+        self.root
+    }
+
+    /// The pitch classes (key modulo 12) of the lower and
+    /// upper notes of the wolf fifth: the eleventh fifth
+    /// stacked above the root, and the root itself.
+    pub const fn wolf_interval(&self) -> (u8, u8) {
+        let root = self.root % 12;
+        ((root + 5) % 12, root)
+    }
+
+    /// Computes the frequency for a given midi key in this
+    /// tuning.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in the range `0..=127`.
+    pub fn frequency(&self, key: u8) -> f32 {
+        ratio_frequency(self.root, key, |degree| {
+            rational(PYTHAGOREAN_RATIOS[degree])
+        })
+    }
+
+    /// Deviation in cents of the given pitch class (0 for C
+    /// through 11 for B) from twelve-tone equal temperament,
+    /// with both tunings agreeing on the root. Relative to
+    /// the root, the deviations are
+    ///
+    /// | Degree | Deviation | Degree | Deviation |
+    /// |-------:|----------:|-------:|----------:|
+    /// |      0 |     0.00¢ |      6 |   +11.73¢ |
+    /// |      1 |   +13.69¢ |      7 |    +1.96¢ |
+    /// |      2 |    +3.91¢ |      8 |   +15.64¢ |
+    /// |      3 |   +17.60¢ |      9 |    +5.87¢ |
+    /// |      4 |    +7.82¢ |     10 |   +19.55¢ |
+    /// |      5 |   +21.51¢ |     11 |    +9.78¢ |
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pitch_class` is not in the range `0..=11`.
+    pub fn deviation_from_et_cents(&self, pitch_class: u8) -> f32 {
+        assert!(
+            pitch_class < 12,
+            "pitch class {} out of range 0..=11",
+            pitch_class,
+        );
+        let degree = (pitch_class + 12 - self.root % 12) % 12;
+        et_deviation_cents(rational(PYTHAGOREAN_RATIOS[degree as usize]), degree)
+    }
+}
+
+impl TuningSystem for PythagoreanTuning {
+    fn key_to_frequency(&self, key: u8) -> f32 {
+        self.frequency(key)
+    }
+}
+
+/// An equal division of the octave into a given number of
+/// steps, such as 19-EDO, 31-EDO or 53-EDO. Steps are
+/// numbered by `i32`, with a reference step at a reference
+/// frequency: by default step 69 is A4 = 440 Hz, so that
+/// step numbers in 0..=127 line up with midi keys. As a
+/// [TuningSystem], each key is the step of the same number.
+///
+/// Each step is `1200 / divisions` cents, so with the
+/// default reference step `69 + n` is `n · (1200 /
+/// divisions - 100)` cents away from the twelve-tone
+/// equal-tempered key `69 + n`. 12-EDO agrees with
+/// [EqualTemperament] to within `f32` rounding: frequencies
+/// are computed in double precision, so that any step
+/// number is handled correctly.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{Edo, TuningSystem};
+/// let edo = Edo::new(31);
+/// assert_eq!(edo.key_to_frequency(69), 440.0);
+/// assert_eq!(edo.frequency(69 + 31), 880.0);
+/// assert_eq!(edo.steps_to_frequency(-31), 220.0);
+/// assert_eq!(edo.nearest_step(880.0), Some((100, 0.0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Edo {
+    divisions: u16,
+    reference_step: i32,
+    reference_hz: f32,
+}
+
+impl Edo {
+    /// Make an equal division of the octave into
+    /// `divisions` steps, with step 69 at 440 Hz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisions` is 0.
+    pub const fn new(divisions: u16) -> Self {
+        Edo::with_reference(divisions, 69, 440.0)
+    }
+
+    /// Make an equal division of the octave into
+    /// `divisions` steps, with step `reference_step` at
+    /// `reference_hz` Hz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisions` is 0, or if `reference_hz` is
+    /// not a finite positive number.
+    pub const fn with_reference(divisions: u16, reference_step: i32, reference_hz: f32) -> Self {
+        assert!(divisions > 0, "EDO divisions must be positive");
+        assert!(
+            reference_hz > 0.0 && reference_hz.is_finite(),
+            "reference frequency must be finite and positive",
+        );
+        Edo {
+            divisions,
+            reference_step,
+            reference_hz,
+        }
+    }
+
+    /// The number of steps per octave.
+    pub const fn divisions(&self) -> u16 {
+        self.divisions
+    }
+
+    /// The reference step and its frequency in Hz.
+    pub const fn reference(&self) -> (i32, f32) {
+        (self.reference_step, self.reference_hz)
+    }
+
+    /// Frequency in Hz of the given step.
+    pub fn frequency(&self, step: i32) -> f32 {
+        self.offset_frequency(step as i64 - self.reference_step as i64)
+    }
+
+    /// Unit period in seconds of the given step.
+    pub fn period(&self, step: i32) -> f32 {
+        1.0 / self.frequency(step)
+    }
+
+    /// Frequency in Hz of the note `steps` steps above the
+    /// reference step. Negative step counts are below it.
+    pub fn steps_to_frequency(&self, steps: i32) -> f32 {
+        self.offset_frequency(steps.into())
+    }
+
+    /// Frequency in Hz `steps` steps above the reference
+    /// step, computed in double precision so that step counts
+    /// beyond the range of an `i32`, or too large to be exact
+    /// in an `f32`, are handled correctly.
+    fn offset_frequency(&self, steps: i64) -> f32 {
+        let octaves = steps as f64 / self.divisions as f64;
+        (self.reference_hz as f64 * f64::powf(2.0, octaves)) as f32
+    }
+
+    /// Finds the step nearest in pitch to the given
+    /// frequency in Hz, returning it with the offset in cents
+    /// of the frequency from that step: positive if the
+    /// frequency is sharp of the step. The offset is at most
+    /// half a step either way.
+    ///
+    /// Returns `None` if `freq` is not a finite positive
+    /// number, or if the step does not fit in an `i32`.
+    pub fn nearest_step(&self, freq: f32) -> Option<(i32, f32)> {
+        if !(freq > 0.0 && freq.is_finite()) {
+            return None;
+        }
+        let divisions = self.divisions as f64;
+        let steps = divisions * f64::log2(freq as f64 / self.reference_hz as f64);
+        let nearest = f64::round(steps);
+        let cents = 1200.0 * (steps - nearest) / divisions;
+        let step = self.reference_step.checked_add(nearest as i32)?;
+        Some((step, cents as f32))
+    }
+}
+
+impl TuningSystem for Edo {
+    fn key_to_frequency(&self, key: u8) -> f32 {
+        let key = or_panic(check_key(key));
+        self.frequency(key as i32)
+    }
+}
+
+/// Frequency of `key` in a tuning given by `ratios` above C
+/// for each pitch class, with A4 at `a4` Hz and other
+/// octaves shifted by factors of two.
+fn pitch_class_frequency(a4: f32, ratios: &[f64; 12], key: u8) -> f32 {
+    let (m, o) = or_panic(try_key_to_params_bottom(key));
+    let c4 = a4 as f64 / ratios[9];
+    (c4 * ratios[m as usize] * f64::powi(2.0, o as i32 - 5)) as f32
+}
+
+/// Werckmeister III ratios above C for each pitch class.
+/// The fifths C–G, G–D, D–A and B–F♯ are each narrowed by a
+/// quarter of the Pythagorean comma; the rest are pure.
+const WERCKMEISTER_III_RATIOS: [f64; 12] = [
+    1.0,
+    256.0 / 243.0,
+    1.117_403_308_541_704_8, // 64/81 · √2
+    32.0 / 27.0,
+    1.252_827_248_727_146_6, // 256/243 · ⁴√2
+    4.0 / 3.0,
+    1024.0 / 729.0,
+    1.494_926_960_451_048, // 8/9 · ⁴√8
+    128.0 / 81.0,
+    1.670_436_331_636_195_2, // 1024/729 · ⁴√2
+    16.0 / 9.0,
+    1.879_240_873_090_719_5, // 128/81 · ⁴√2
+];
+
+/// Andreas Werckmeister's "correct temperament No. 3", a
+/// well temperament for baroque keyboard music in which
+/// every key is usable but each has its own character. It
+/// is built on C, with A4 at a reference frequency that
+/// defaults to the baroque pitch of 415 Hz.
+///
+/// | Pitch class | Ratio           | Deviation from 12-TET |
+/// |------------:|----------------:|----------------------:|
+/// |           C |             1/1 |                 0.00¢ |
+/// |          C♯ |         256/243 |                -9.78¢ |
+/// |           D |      64/81 · √2 |                -7.82¢ |
+/// |          D♯ |           32/27 |                -5.87¢ |
+/// |           E |   256/243 · ⁴√2 |                -9.78¢ |
+/// |           F |             4/3 |                -1.96¢ |
+/// |          F♯ |        1024/729 |               -11.73¢ |
+/// |           G |       8/9 · ⁴√8 |                -3.91¢ |
+/// |          G♯ |          128/81 |                -7.82¢ |
+/// |           A |  1024/729 · ⁴√2 |               -11.73¢ |
+/// |          A♯ |            16/9 |                -3.91¢ |
+/// |           B |    128/81 · ⁴√2 |                -7.82¢ |
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{TuningSystem, WerckmeisterIII};
+/// let tuning = WerckmeisterIII::default();
+/// assert_eq!(tuning.key_to_frequency(69), 415.0);
+/// let fifth = tuning.key_to_frequency(67) / tuning.key_to_frequency(60);
+/// assert!((fifth - 1.4949).abs() < 0.0001);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WerckmeisterIII {
+    a4: f32,
+}
+
+impl WerckmeisterIII {
+    /// Make a Werckmeister III tuning with A4 at the given
+    /// frequency in Hz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a4` is not a finite positive number.
+    pub const fn with_reference(a4: f32) -> Self {
+        assert!(a4 > 0.0 && a4.is_finite(), "a4 must be finite and positive");
+        WerckmeisterIII { a4 }
+    }
+
+    /// Reference frequency in Hz of A4 for this tuning.
+    pub const fn a4(&self) -> f32 {
+        self.a4
+    }
+}
+
+impl Default for WerckmeisterIII {
+    fn default() -> Self {
+        WerckmeisterIII::with_reference(ConcertPitch::Baroque.a4_hz())
+    }
+}
+
+impl TuningSystem for WerckmeisterIII {
+    fn key_to_frequency(&self, key: u8) -> f32 {
+        pitch_class_frequency(self.a4, &WERCKMEISTER_III_RATIOS, key)
+    }
+}
+
+/// Quarter-comma meantone ratios above C for each pitch
+/// class, found by stacking fifths of ⁴√5 from E♭ up to G♯
+/// and reducing each into C's octave.
+const QUARTER_COMMA_MEANTONE_RATIOS: [f64; 12] = [
+    1.0,
+    1.044_906_726_525_659_3,
+    1.118_033_988_749_894_7, // √5 / 2
+    1.196_279_024_976_976_6,
+    1.25,
+    1.337_480_609_952_844,
+    1.397_542_485_937_368_4,
+    1.495_348_781_221_220_5, // ⁴√5
+    1.5625,
+    1.671_850_762_441_055,
+    1.788_854_381_999_832, // 4 / √5
+    1.869_185_976_526_525_5,
+];
+
+/// Quarter-comma meantone, the dominant keyboard tuning of
+/// the 15th through 17th centuries. Every fifth but one is
+/// narrowed by a quarter of the syntonic comma to ⁴√5
+/// (about 1.49535), so that the major thirds C–E, D–F♯, E♭–G,
+/// E–G♯, F–A, G–B, A–C♯ and B♭–D are a pure 5/4. The fifths
+/// are stacked from E♭ up to G♯ on C, leaving a "wolf"
+/// fifth from G♯ to E♭ that is sharp, at about 737.64¢
+/// (ratio 1.5312). A4 is at a reference frequency that
+/// defaults to 440 Hz.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{QuarterCommaMeantone, TuningSystem};
+/// let tuning = QuarterCommaMeantone::default();
+/// assert_eq!(tuning.key_to_frequency(69), 440.0);
+/// let third = tuning.key_to_frequency(64) / tuning.key_to_frequency(60);
+/// assert!((third - 1.25).abs() < 1.0e-6);
+/// assert_eq!(tuning.deviation_from_et_cents(4).round(), -14.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuarterCommaMeantone {
+    a4: f32,
+}
+
+impl QuarterCommaMeantone {
+    /// Make a quarter-comma meantone tuning with A4 at the
+    /// given frequency in Hz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a4` is not a finite positive number.
+    pub const fn with_reference(a4: f32) -> Self {
+        assert!(a4 > 0.0 && a4.is_finite(), "a4 must be finite and positive");
+        QuarterCommaMeantone { a4 }
+    }
+
+    /// Reference frequency in Hz of A4 for this tuning.
+    pub const fn a4(&self) -> f32 {
+        self.a4
+    }
+
+    /// Deviation in cents of the given pitch class (0 for C
+    /// through 11 for B) from twelve-tone equal temperament,
+    /// with both tunings agreeing on C.
+    ///
+    /// | Pitch class | Deviation | Pitch class | Deviation |
+    /// |------------:|----------:|------------:|----------:|
+    /// |           C |     0.00¢ |          F♯ |   -20.53¢ |
+    /// |          C♯ |   -23.95¢ |           G |    -3.42¢ |
+    /// |           D |    -6.84¢ |          G♯ |   -27.37¢ |
+    /// |          E♭ |   +10.26¢ |           A |   -10.26¢ |
+    /// |           E |   -13.69¢ |          B♭ |    +6.84¢ |
+    /// |           F |    +3.42¢ |           B |   -17.11¢ |
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pitch_class` is not in the range `0..=11`.
+    pub fn deviation_from_et_cents(&self, pitch_class: u8) -> f32 {
+        assert!(
+            pitch_class < 12,
+            "pitch class {} out of range 0..=11",
+            pitch_class,
+        );
+        et_deviation_cents(
+            QUARTER_COMMA_MEANTONE_RATIOS[pitch_class as usize],
+            pitch_class,
+        )
+    }
+}
+
+impl Default for QuarterCommaMeantone {
+    fn default() -> Self {
+        QuarterCommaMeantone::with_reference(440.0)
+    }
+}
+
+impl TuningSystem for QuarterCommaMeantone {
+    fn key_to_frequency(&self, key: u8) -> f32 {
+        pitch_class_frequency(self.a4, &QUARTER_COMMA_MEANTONE_RATIOS, key)
+    }
+}
+
+/// A meantone temperament built on a root key, with every
+/// fifth but one narrowed from a pure 3/2 by a fixed
+/// fraction of the syntonic comma (81/80). The root keeps
+/// its equal-tempered frequency, and the fifths are stacked
+/// from three below the root to eight above it, as E♭ to G♯
+/// on C. This leaves a "wolf" fifth from the eighth fifth
+/// above the root back to the third below it; see
+/// [Meantone::wolf_interval]. Other octaves are shifted by
+/// factors of two.
+///
+/// The [default](Meantone::default) is quarter-comma
+/// meantone on middle C, whose fifths of ⁴√5 make the major
+/// thirds on the root, and on seven other notes, a pure
+/// 5/4. Third-comma meantone (fraction 1/3) instead makes
+/// the minor thirds pure, and a fraction of zero gives pure
+/// fifths throughout but the wolf.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{Meantone, TuningSystem};
+/// let tuning = Meantone::default();
+/// let third = tuning.frequency(64) / tuning.frequency(60);
+/// assert!((third - 1.25).abs() < 1.0e-6);
+/// assert_eq!(tuning.wolf_interval(), (8, 3));
+/// assert_eq!(tuning.deviation_from_et_cents(4).round(), -14.0);
+/// assert_eq!(tuning.key_to_frequency(60), keytones::key_to_frequency_raw(60));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Meantone {
+    root: u8,
+    fraction: f32,
+    ratios: [f64; 12],
+}
+
+impl Meantone {
+    /// Make a meantone temperament on the given root key,
+    /// with fifths narrowed by `fraction` of a syntonic
+    /// comma.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root` is not in the range `0..=127`, or if
+    /// `fraction` is not finite.
+    pub fn new(root: u8, fraction: f32) -> Self {
+        let root = or_panic(check_key(root));
+        assert!(fraction.is_finite(), "fraction must be finite");
+        let fifth = 1.5 * f64::powf(81.0 / 80.0, -fraction as f64);
+        let mut ratios = [1.0; 12];
+        for i in -3..=8 {
+            let ratio = f64::powi(fifth, i);
+            let octaves = f64::floor(f64::log2(ratio));
+            ratios[(7 * i).rem_euclid(12) as usize] = ratio / f64::powf(2.0, octaves);
+        }
+        Meantone {
+            root,
+            fraction,
+            ratios,
+        }
+    }
+
+    /// Make a quarter-comma meantone temperament on the
+    /// given root key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root` is not in the range `0..=127`.
+    pub fn quarter_comma(root: u8) -> Self {
+        Meantone::new(root, 0.25)
+    }
+
+    /// The root key of this tuning.
+    pub const fn root(&self) -> u8 {
+        self.root
+    }
+
+    /// The fraction of a syntonic comma by which the fifths
+    /// of this tuning are narrowed.
+    pub const fn fraction(&self) -> f32 {
+        self.fraction
+    }
+
+    /// The pitch classes (key modulo 12) of the lower and
+    /// upper notes of the wolf fifth: the eighth fifth
+    /// stacked above the root, and the third below it. For
+    /// quarter-comma meantone the wolf is about 737.64¢, a
+    /// ratio of 1.5312.
+    pub const fn wolf_interval(&self) -> (u8, u8) {
+        let root = self.root % 12;
+        ((root + 8) % 12, (root + 3) % 12)
+    }
+
+    /// Computes the frequency for a given midi key in this
+    /// tuning.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in the range `0..=127`.
+    pub fn frequency(&self, key: u8) -> f32 {
+        ratio_frequency(self.root, key, |degree| self.ratios[degree])
+    }
+
+    /// Deviation in cents of the given pitch class (0 for C
+    /// through 11 for B) from twelve-tone equal temperament,
+    /// with both tunings agreeing on the root. For
+    /// quarter-comma meantone on C, these are as given for
+    /// [QuarterCommaMeantone::deviation_from_et_cents].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pitch_class` is not in the range `0..=11`.
+    pub fn deviation_from_et_cents(&self, pitch_class: u8) -> f32 {
+        assert!(
+            pitch_class < 12,
+            "pitch class {} out of range 0..=11",
+            pitch_class,
+        );
+        let degree = (pitch_class + 12 - self.root % 12) % 12;
+        et_deviation_cents(self.ratios[degree as usize], degree)
+    }
+}
+
+impl Default for Meantone {
+    fn default() -> Self {
+        Meantone::quarter_comma(60)
+    }
+}
+
+impl TuningSystem for Meantone {
+    fn key_to_frequency(&self, key: u8) -> f32 {
+        self.frequency(key)
+    }
+}
+
+/// An equal-tempered tuning with its octaves stretched, as
+/// acoustic pianos are tuned to match the inharmonicity of
+/// their strings. A key $d$ octaves from a center key is
+/// detuned by
+///    $$t \cdot d^p$$
+/// cents above the center, or
+///    $$-b \cdot |d|^p$$
+/// cents below it, where $t$ and $b$ are the treble and bass
+/// stretch in cents at one octave and $p$ is the exponent of
+/// the curve. The center key is unstretched, and since the
+/// stretch never decreases with the key, neither does the
+/// frequency.
+///
+/// [StretchTuning::RAILSBACK] approximates the Railsback
+/// curve of a well-tuned grand piano.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{key_to_frequency_raw, StretchTuning};
+/// let tuning = StretchTuning::linear(2.0);
+/// assert_eq!(tuning.frequency(69), 440.0);
+/// assert_eq!(tuning.deviation_cents(81), 2.0);
+/// assert!(tuning.frequency(81) > key_to_frequency_raw(81));
+/// assert_eq!(StretchTuning::RAILSBACK.deviation_cents(21), -32.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StretchTuning {
+    center: u8,
+    bass_cents: f32,
+    treble_cents: f32,
+    exponent: f32,
+}
+
+impl StretchTuning {
+    /// A Railsback-like piano stretch centered on A4, with a
+    /// quadratic curve reaching -32¢ at A0 (key 21) and about
+    /// +32¢ at C8 (key 108).
+    pub const RAILSBACK: StretchTuning = StretchTuning::new(69, 2.0, 3.0, 2.0);
+
+    /// Make a stretch tuning centered on the given key, with
+    /// the given bass and treble stretch in cents at one
+    /// octave from the center and the given curve exponent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `center` is not in the range `0..=127`, if
+    /// `bass_cents` or `treble_cents` is not a finite
+    /// non-negative number, or if `exponent` is not a finite
+    /// positive number.
+    pub const fn new(center: u8, bass_cents: f32, treble_cents: f32, exponent: f32) -> Self {
+        assert!(center <= 127, "center key out of range 0..=127");
+        assert!(
+            bass_cents >= 0.0 && bass_cents.is_finite(),
+            "bass stretch must be finite and non-negative",
+        );
+        assert!(
+            treble_cents >= 0.0 && treble_cents.is_finite(),
+            "treble stretch must be finite and non-negative",
+        );
+        assert!(
+            exponent > 0.0 && exponent.is_finite(),
+            "stretch exponent must be finite and positive",
+        );
+        StretchTuning {
+            center,
+            bass_cents,
+            treble_cents,
+            exponent,
+        }
+    }
+
+    /// Make a stretch tuning centered on A4 (key 69) that
+    /// stretches every octave by the same number of cents,
+    /// symmetrically in bass and treble.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cents_per_octave` is not a finite
+    /// non-negative number.
+    pub const fn linear(cents_per_octave: f32) -> Self {
+        StretchTuning::new(69, cents_per_octave, cents_per_octave, 1.0)
+    }
+
+    /// The unstretched center key of this tuning.
+    pub const fn center(&self) -> u8 {
+        self.center
+    }
+
+    /// Deviation in cents of the given midi key from
+    /// twelve-tone equal temperament in this tuning.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in the range `0..=127`.
+    pub fn deviation_cents(&self, key: u8) -> f32 {
+        let key = or_panic(check_key(key));
+        let octaves = (key as f32 - self.center as f32) / 12.0;
+        if octaves > 0.0 {
+            self.treble_cents * f32::powf(octaves, self.exponent)
+        } else if octaves < 0.0 {
+            -self.bass_cents * f32::powf(-octaves, self.exponent)
+        } else {
+            0.0
+        }
+    }
+
+    /// Computes the frequency for a given midi key in this
+    /// tuning.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in the range `0..=127`.
+    pub fn frequency(&self, key: u8) -> f32 {
+        let cents = self.deviation_cents(key);
+        if cents == 0.0 {
+            return key_to_frequency_raw(key);
+        }
+        key_to_frequency_raw(key) * f32::powf(2.0, cents / 1200.0)
+    }
+}
+
+impl TuningSystem for StretchTuning {
+    fn key_to_frequency(&self, key: u8) -> f32 {
+        self.frequency(key)
+    }
+}
+
+/// An equal-tempered tuning in which key 69 (A4) has a given
+/// reference frequency in Hz. All keys are scaled
+/// proportionally from the standard 440 Hz tuning.
+///
+/// The [Default] tuning uses the standard A4 = 440 Hz, and
+/// gives results bit-identical to the free functions of
+/// this crate.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::Tuning;
+/// let tuning = Tuning::new(442.0);
+/// assert_eq!(tuning.frequency(69), 442.0);
+/// assert_eq!(tuning.frequency(57), 221.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tuning {
+    a4: f32,
+}
+
+impl Tuning {
+    /// Make a tuning with A4 at the given frequency in Hz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a4` is not a finite positive number.
+    pub const fn new(a4: f32) -> Self {
+        assert!(a4 > 0.0 && a4.is_finite(), "a4 must be finite and positive");
+        Tuning { a4 }
+    }
+
+    /// Reference frequency in Hz of A4 for this tuning.
+    pub const fn a4(&self) -> f32 {
+        self.a4
+    }
+
+    /// Computes the frequency for a given midi key in this
+    /// tuning. See [key_to_frequency_raw].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in the range `0..=127`.
+    pub fn frequency(&self, key: u8) -> f32 {
+        key_to_frequency_raw(key) * (self.a4 / 440.0)
+    }
+
+    /// Computes the unit period for a given midi key in this
+    /// tuning. See [key_to_period_raw].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in the range `0..=127`.
+    pub fn period(&self, key: u8) -> f32 {
+        key_to_period_raw(key) * (440.0 / self.a4)
+    }
+
+    /// Computes the approximate frequency for a given midi
+    /// key in this tuning. See [key_to_frequency_approx_raw].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in the range `0..=127`.
+    pub fn frequency_approx(&self, key: u8) -> f32 {
+        key_to_frequency_approx_raw(key) * (self.a4 / 440.0)
+    }
+
+    /// Computes the approximate unit period for a given midi
+    /// key in this tuning. See [key_to_period_approx_raw].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in the range `0..=127`.
+    pub fn period_approx(&self, key: u8) -> f32 {
+        key_to_period_approx_raw(key) * (440.0 / self.a4)
+    }
+}
+
+/// A common concert pitch: the reference frequency of A4.
+/// Each preset converts into the equal-tempered [Tuning]
+/// with that reference.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{ConcertPitch, Tuning};
+/// assert_eq!(ConcertPitch::Baroque.a4_hz(), 415.0);
+/// let tuning = Tuning::from(ConcertPitch::A442);
+/// assert_eq!(tuning.frequency(69), 442.0);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ConcertPitch {
+    /// A4 = 415 Hz, the pitch conventionally used for
+    /// baroque music on period instruments: a semitone
+    /// below 440 Hz (415.3 Hz), rounded down.
+    Baroque,
+    /// A4 = 430 Hz, the approximate pitch of the classical
+    /// era, used for period performances of Mozart and
+    /// Beethoven.
+    Classical,
+    /// A4 = 432 Hz, the "Verdi" pitch, proposed in 1884 and
+    /// popular with some modern performers.
+    A432,
+    /// A4 = 440 Hz, the international standard pitch of
+    /// ISO 16, as used by the rest of this crate. This is
+    /// the [Default].
+    #[default]
+    Standard,
+    /// A4 = 442 Hz, the pitch of many continental European
+    /// and American orchestras.
+    A442,
+    /// A4 = 444 Hz, a bright pitch used by some European
+    /// orchestras.
+    A444,
+    /// Any other pitch, given in Hz.
+    Custom(f32),
+}
+
+impl ConcertPitch {
+    /// Reference frequency of A4 in Hz for this pitch.
+    pub const fn a4_hz(&self) -> f32 {
+        match self {
+            ConcertPitch::Baroque => 415.0,
+            ConcertPitch::Classical => 430.0,
+            ConcertPitch::A432 => 432.0,
+            ConcertPitch::Standard => 440.0,
+            ConcertPitch::A442 => 442.0,
+            ConcertPitch::A444 => 444.0,
+            ConcertPitch::Custom(a4) => *a4,
+        }
+    }
+}
+
+/// Panics if a [ConcertPitch::Custom] pitch is not a
+/// finite positive number.
+impl From<ConcertPitch> for Tuning {
+    fn from(pitch: ConcertPitch) -> Self {
+        Tuning::new(pitch.a4_hz())
+    }
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Tuning::new(440.0)
+    }
+}
+
+impl TuningSystem for Tuning {
+    fn key_to_frequency(&self, key: u8) -> f32 {
+        self.frequency(key)
+    }
+}
+
+/// Computes the frequency for a given midi key with A4 at
+/// the given reference frequency `a4` in Hz. This is
+/// shorthand for `Tuning::new(a4).frequency(key)`.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_frequency_with_reference;
+/// assert_eq!(key_to_frequency_with_reference(69, 432.0), 432.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`, or if
+/// `a4` is not a finite positive number.
+pub fn key_to_frequency_with_reference(key: u8, a4: f32) -> f32 {
+    Tuning::new(a4).frequency(key)
+}
+
+/// Computes the unit period for a given midi key with A4 at
+/// the given reference frequency `a4` in Hz. This is
+/// shorthand for `Tuning::new(a4).period(key)`.
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`, or if
+/// `a4` is not a finite positive number.
+pub fn key_to_period_with_reference(key: u8, a4: f32) -> f32 {
+    Tuning::new(a4).period(key)
+}
+
+/// Computes the approximate frequency for a given midi key
+/// with A4 at the given reference frequency `a4` in Hz. This
+/// is shorthand for `Tuning::new(a4).frequency_approx(key)`.
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`, or if
+/// `a4` is not a finite positive number.
+pub fn key_to_frequency_approx_with_reference(key: u8, a4: f32) -> f32 {
+    Tuning::new(a4).frequency_approx(key)
+}
+
+/// Computes the approximate unit period for a given midi key
+/// with A4 at the given reference frequency `a4` in Hz. This
+/// is shorthand for `Tuning::new(a4).period_approx(key)`.
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`, or if
+/// `a4` is not a finite positive number.
+pub fn key_to_period_approx_with_reference(key: u8, a4: f32) -> f32 {
+    Tuning::new(a4).period_approx(key)
+}
+
+#[test]
+fn test_default_tuning() {
+    let tuning = Tuning::default();
+    for k in 0..=127 {
+        assert_eq!(tuning.frequency(k), key_to_frequency_raw(k));
+        assert_eq!(tuning.period(k), key_to_period_raw(k));
+        assert_eq!(tuning.frequency_approx(k), key_to_frequency_approx_raw(k));
+        assert_eq!(tuning.period_approx(k), key_to_period_approx_raw(k));
+    }
+}
+
+#[test]
+fn test_tuning() {
+    const TUNING: Tuning = Tuning::new(442.0);
+    assert_eq!(TUNING.frequency(69), 442.0);
+    assert_eq!(TUNING.frequency_approx(69).round(), 442.0);
+    assert_eq!((TUNING.period(69) * 442.0).round(), 1.0);
+    for k in 0..=115 {
+        let f0 = TUNING.frequency(k);
+        let f1 = TUNING.frequency(k + 12);
+        assert!(f32::abs(f1 - 2.0 * f0) < 1.0e-5 * f1, "{} {} {}", k, f0, f1);
+    }
+}
+
+#[test]
+#[should_panic(expected = "a4 must be finite and positive")]
+fn test_tuning_invalid() {
+    Tuning::new(0.0);
+}
+
+#[test]
+fn test_with_reference() {
+    for k in 0..=127 {
+        assert_eq!(
+            key_to_frequency_with_reference(k, 440.0),
+            key_to_frequency_raw(k)
+        );
+        assert_eq!(key_to_period_with_reference(k, 440.0), key_to_period_raw(k));
+        let tuning = Tuning::new(432.0);
+        assert_eq!(
+            key_to_frequency_with_reference(k, 432.0),
+            tuning.frequency(k)
+        );
+        assert_eq!(key_to_period_with_reference(k, 432.0), tuning.period(k));
+        assert_eq!(
+            key_to_frequency_approx_with_reference(k, 432.0),
+            tuning.frequency_approx(k),
+        );
+        assert_eq!(
+            key_to_period_approx_with_reference(k, 432.0),
+            tuning.period_approx(k),
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "a4 must be finite and positive")]
+fn test_with_reference_invalid() {
+    key_to_frequency_with_reference(69, f32::NAN);
+}
+
+#[test]
+fn test_tuning_system() {
+    let table = TuningTable::new(crate::FREQUENCY_TABLE);
+    let systems: [&dyn TuningSystem; 3] = [&EqualTemperament, &Tuning::default(), &table];
+    for system in systems {
+        for k in 0..=127 {
+            let f = system.key_to_frequency(k);
+            assert!(
+                f32::abs(f - key_to_frequency_raw(k)) < 1.0e-6 * f,
+                "{} {}",
+                k,
+                f
+            );
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "key 128 out of range 0..=127")]
+fn test_tuning_table_out_of_range() {
+    TuningTable::new(crate::FREQUENCY_TABLE).key_to_frequency(128);
+}
+
+#[test]
+fn test_just_intonation() {
+    let tuning = JustIntonation::new(60);
+    let c = key_to_frequency_raw(60);
+    assert_eq!(tuning.key_to_frequency(60), c);
+    assert_eq!(tuning.key_to_frequency(64), c * 5.0 / 4.0);
+    assert_eq!(tuning.key_to_frequency(67), c * 3.0 / 2.0);
+    assert_eq!(tuning.key_to_frequency(72), c * 2.0);
+    assert_eq!(tuning.key_to_frequency(55), c * 3.0 / 4.0);
+    assert_eq!(tuning.key_to_frequency(83), c * 15.0 / 4.0);
+
+    let cents = [
+        0.0, 11.73, 3.91, 15.64, -13.69, -1.96, -9.78, 1.96, 13.69, -15.64, 17.60, -11.73,
+    ];
+    for k in 0..=127 {
+        let f = tuning.key_to_frequency(k);
+        let deviation = 1200.0 * f32::log2(f / key_to_frequency_raw(k));
+        // The root 60 is a multiple of 12.
+        let expected = cents[k as usize % 12];
+        assert!(f32::abs(deviation - expected) < 0.01, "{} {}", k, deviation);
+        assert_eq!(tuning.frequency(k), f);
+    }
+
+    // The root and its octaves are unchanged from 12-TET,
+    // and the major third is 13.7 cents flat, for any root.
+    for root in [0, 21, 62, 69, 127] {
+        let tuning = JustIntonation::new(root);
+        for k in (root % 12..=127).step_by(12) {
+            let f = key_to_frequency_raw(k);
+            assert!(
+                f32::abs(tuning.frequency(k) - f) <= 1.0e-6 * f,
+                "{} {}",
+                root,
+                k
+            );
+            assert_eq!(tuning.ratio(k), (1, 1));
+        }
+        let k = if root <= 123 { root + 4 } else { root - 8 };
+        let third = 1200.0 * f32::log2(tuning.frequency(k) / key_to_frequency_raw(k));
+        assert!(f32::abs(third + 13.69) < 0.01, "{} {}", root, third);
+        assert_eq!(tuning.ratio(k), (5, 4));
+    }
+
+    for (i, &(num, den)) in JUST_RATIOS.iter().enumerate() {
+        let gcd = (1..=den).rev().find(|d| num % d == 0 && den % d == 0);
+        assert_eq!(gcd, Some(1));
+        let cents = 1200.0 * f64::log2(num as f64 / den as f64);
+        assert!(f64::abs(cents - 100.0 * i as f64) < 20.0);
+    }
+}
+
+#[test]
+fn test_pythagorean_tuning() {
+    assert_eq!(PYTHAGOREAN_RATIOS[5], (177147, 131072));
+    assert_eq!(PYTHAGOREAN_RATIOS[7], (3, 2));
+
+    let tuning = PythagoreanTuning::new(62);
+    let d = key_to_frequency_raw(62);
+    assert_eq!(tuning.key_to_frequency(62), d);
+    assert_eq!(tuning.key_to_frequency(74), 2.0 * d);
+    assert_eq!(tuning.wolf_interval(), (7, 2));
+
+    // Every fifth but the wolf is pure.
+    let (wolf_lo, _) = tuning.wolf_interval();
+    for k in 0..=120 {
+        let ratio = tuning.key_to_frequency(k + 7) / tuning.key_to_frequency(k);
+        if k % 12 == wolf_lo {
+            let cents = 1200.0 * f32::log2(ratio);
+            assert!(f32::abs(cents - 678.49) < 0.01, "{} {}", k, cents);
+        } else {
+            assert!(f32::abs(ratio - 1.5) < 1.0e-6, "{} {}", k, ratio);
+        }
+    }
+
+    // The pitch classes follow the tuning and agree with
+    // the actual frequencies.
+    let cents = [
+        0.0, 13.69, 3.91, 17.60, 7.82, 21.51, 11.73, 1.96, 15.64, 5.87, 19.55, 9.78,
+    ];
+    for root in [0, 62, 127] {
+        let tuning = PythagoreanTuning::new(root);
+        assert_eq!(tuning.frequency(root), key_to_frequency_raw(root));
+        for k in 0..=127 {
+            let f = tuning.frequency(k);
+            assert_eq!(f, tuning.key_to_frequency(k));
+            let deviation = tuning.deviation_from_et_cents(k % 12);
+            let expected = cents[(k as usize + 12 - root as usize % 12) % 12];
+            assert!(f32::abs(deviation - expected) < 0.01, "{} {}", k, deviation);
+            let actual = 1200.0 * f32::log2(f / key_to_frequency_raw(k));
+            assert!(f32::abs(actual - deviation) < 0.01, "{} {}", k, actual);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "pitch class 12 out of range 0..=11")]
+fn test_pythagorean_tuning_bad_pitch_class() {
+    PythagoreanTuning::new(60).deviation_from_et_cents(12);
+}
+
+#[test]
+fn test_edo() {
+    use crate::key_to_frequency_f64;
+
+    let edo = Edo::new(12);
+    assert_eq!(edo.reference(), (69, 440.0));
+    for k in 0..=127 {
+        let f = key_to_frequency_raw(k);
+        assert!(f32::abs(edo.key_to_frequency(k) - f) <= 2.0e-7 * f, "{}", k);
+        assert_eq!(edo.frequency(k as i32), key_to_frequency_f64(k) as f32);
+        let p = edo.period(k as i32);
+        assert!(
+            f32::abs(p - crate::key_to_period_raw(k)) < 1.0e-6 * p,
+            "{}",
+            k
+        );
+        let (step, cents) = edo.nearest_step(key_to_frequency_raw(k)).unwrap();
+        assert_eq!(step, k as i32);
+        assert!(cents.abs() < 0.001, "{} {}", k, cents);
+    }
+
+    for divisions in [19, 31, 53] {
+        let edo = Edo::new(divisions);
+        let step = 1200.0 / divisions as f32;
+        assert_eq!(edo.steps_to_frequency(divisions as i32), 880.0);
+        assert_eq!(edo.steps_to_frequency(-2 * divisions as i32), 110.0);
+        for k in 0..=127 {
+            let cents = 1200.0 * f32::log2(edo.key_to_frequency(k) / key_to_frequency_raw(k));
+            let expected = (k as f32 - 69.0) * (step - 100.0);
+            assert!(
+                f32::abs(cents - expected) < 0.01,
+                "{} {} {}",
+                divisions,
+                k,
+                cents
+            );
+        }
+        for s in -200..=200 {
+            let f = edo.frequency(s) * f32::powf(2.0, 0.3 * step / 1200.0);
+            let (nearest, cents) = edo.nearest_step(f).unwrap();
+            assert_eq!(nearest, s);
+            assert!(f32::abs(cents - 0.3 * step) < 0.01, "{} {}", s, cents);
+        }
+    }
+    assert!(Edo::new(1).steps_to_frequency(-100) > 0.0);
+
+    // Interval sizes in cents from published tables.
+    let cents = |edo: Edo, steps: i32| 1200.0 * f32::log2(edo.frequency(steps) / edo.frequency(0));
+    let tests = [
+        (19, 1, 63.16),
+        (19, 6, 378.95),
+        (19, 11, 694.74),
+        (31, 1, 38.71),
+        (31, 10, 387.10),
+        (31, 18, 696.77),
+        (53, 31, 701.89),
+    ];
+    for (divisions, steps, expected) in tests {
+        let c = cents(Edo::new(divisions), steps);
+        assert!(
+            f32::abs(c - expected) < 0.01,
+            "{} {} {}",
+            divisions,
+            steps,
+            c
+        );
+    }
+
+    let edo = Edo::with_reference(19, 0, 261.625_58);
+    assert_eq!(edo.frequency(0), 261.625_58);
+    assert_eq!(edo.frequency(19), 2.0 * 261.625_58);
+    assert_eq!(edo.nearest_step(261.625_58), Some((0, 0.0)));
+    assert_eq!(edo.nearest_step(0.0), None);
+    assert_eq!(edo.nearest_step(f32::NAN), None);
+    assert_eq!(edo.nearest_step(f32::INFINITY), None);
+
+    // Steps beyond the ends of `i32`.
+    let edo = Edo::with_reference(12, i32::MAX - 1, 440.0);
+    assert_eq!(edo.nearest_step(440.0), Some((i32::MAX - 1, 0.0)));
+    assert_eq!(edo.nearest_step(1000.0), None);
+    assert_eq!(edo.nearest_step(220.0).map(|(s, _)| s), Some(i32::MAX - 13));
+    let edo = Edo::with_reference(12, i32::MIN + 1, 440.0);
+    assert_eq!(edo.nearest_step(440.0), Some((i32::MIN + 1, 0.0)));
+    assert_eq!(edo.nearest_step(100.0), None);
+    assert_eq!(edo.nearest_step(880.0).map(|(s, _)| s), Some(i32::MIN + 13));
+
+    // Differences between steps beyond the range of `i32`.
+    let edo = Edo::with_reference(12, i32::MAX - 1, 440.0);
+    assert_eq!(edo.frequency(i32::MIN), 0.0);
+    assert_eq!(edo.frequency(i32::MAX - 13), 220.0);
+    assert!(f32::abs(edo.frequency(i32::MAX) - 466.163_76) < 1.0e-3);
+    let edo = Edo::with_reference(12, i32::MIN + 1, 440.0);
+    assert_eq!(edo.frequency(i32::MAX), f32::INFINITY);
+    assert_eq!(edo.frequency(i32::MIN + 13), 880.0);
+}
+
+#[test]
+#[should_panic(expected = "EDO divisions must be positive")]
+fn test_edo_zero() {
+    Edo::new(0);
+}
+
+#[test]
+fn test_werckmeister_iii() {
+    let tuning = WerckmeisterIII::default();
+    assert_eq!(tuning.a4(), 415.0);
+    assert_eq!(tuning.key_to_frequency(69), 415.0);
+    assert_eq!(tuning.key_to_frequency(57), 207.5);
+
+    // Deviations from 12-TET of C through B, with C tuned to
+    // 12-TET rather than A.
+    let cents = [
+        0.0, -9.78, -7.82, -5.87, -9.78, -1.96, -11.73, -3.91, -7.82, -11.73, -3.91, -7.82,
+    ];
+    let c4 = tuning.key_to_frequency(60);
+    for k in 0..=127 {
+        let f = tuning.key_to_frequency(k);
+        let et = c4 * f32::powf(2.0, (k as f32 - 60.0) / 12.0);
+        let deviation = 1200.0 * f32::log2(f / et);
+        assert!(
+            f32::abs(deviation - cents[k as usize % 12]) < 0.01,
+            "{} {}",
+            k,
+            deviation
+        );
+    }
+
+    // The fifths C–G, G–D, D–A and B–F♯ are narrowed by a
+    // quarter of the Pythagorean comma; the rest are pure.
+    for k in 0..=120 {
+        let ratio = tuning.key_to_frequency(k + 7) / tuning.key_to_frequency(k);
+        let expected = match k % 12 {
+            0 | 2 | 7 | 11 => 1.494_927,
+            _ => 1.5,
+        };
+        assert!(f32::abs(ratio - expected) < 1.0e-5, "{} {}", k, ratio);
+    }
+
+    let tuning = WerckmeisterIII::with_reference(440.0);
+    assert_eq!(tuning.key_to_frequency(69), 440.0);
+}
+
+#[test]
+#[should_panic(expected = "a4 must be finite and positive")]
+fn test_werckmeister_iii_invalid() {
+    WerckmeisterIII::with_reference(0.0);
+}
+
+#[test]
+fn test_quarter_comma_meantone() {
+    let tuning = QuarterCommaMeantone::default();
+    assert_eq!(tuning.a4(), 440.0);
+    assert_eq!(tuning.key_to_frequency(69), 440.0);
+    assert_eq!(tuning.key_to_frequency(57), 220.0);
+
+    let cents = [
+        0.0, -23.95, -6.84, 10.26, -13.69, 3.42, -20.53, -3.42, -27.37, -10.26, 6.84, -17.11,
+    ];
+    let c4 = tuning.key_to_frequency(60);
+    for k in 0..=127 {
+        let f = tuning.key_to_frequency(k);
+        let et = c4 * f32::powf(2.0, (k as f32 - 60.0) / 12.0);
+        let deviation = 1200.0 * f32::log2(f / et);
+        let expected = tuning.deviation_from_et_cents(k % 12);
+        assert!(f32::abs(expected - cents[k as usize % 12]) < 0.01);
+        assert!(f32::abs(deviation - expected) < 0.01, "{} {}", k, deviation);
+    }
+
+    // Every fifth but the wolf from G♯ to E♭ is ⁴√5, and the
+    // major thirds on the notes from E♭ to E are pure.
+    for k in 0..=120 {
+        let ratio = tuning.key_to_frequency(k + 7) / tuning.key_to_frequency(k);
+        let expected = if k % 12 == 8 { 1.531_237 } else { 1.495_349 };
+        assert!(f32::abs(ratio - expected) < 1.0e-5, "{} {}", k, ratio);
+        let third = tuning.key_to_frequency(k + 4) / tuning.key_to_frequency(k);
+        if [3, 10, 5, 0, 7, 2, 9, 4].contains(&(k % 12)) {
+            assert!(f32::abs(third - 1.25) < 1.0e-5, "{} {}", k, third);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "pitch class 12 out of range 0..=11")]
+fn test_quarter_comma_meantone_bad_pitch_class() {
+    QuarterCommaMeantone::default().deviation_from_et_cents(12);
+}
+
+#[test]
+fn test_meantone() {
+    let tuning = Meantone::default();
+    assert_eq!((tuning.root(), tuning.fraction()), (60, 0.25));
+    assert_eq!(tuning, Meantone::quarter_comma(60));
+
+    // Published quarter-comma meantone on C, in cents above C.
+    let cents = [
+        0.0, 76.05, 193.16, 310.26, 386.31, 503.42, 579.47, 696.58, 772.63, 889.74, 1006.84,
+        1082.89,
+    ];
+    let qcm = QuarterCommaMeantone::default();
+    for pc in 0..12 {
+        let deviation = tuning.deviation_from_et_cents(pc);
+        let expected = cents[pc as usize] - 100.0 * pc as f32;
+        assert!(
+            f32::abs(deviation - expected) < 0.01,
+            "{} {}",
+            pc,
+            deviation
+        );
+        assert!(f32::abs(deviation - qcm.deviation_from_et_cents(pc)) < 1.0e-4);
+    }
+
+    for root in [0, 57, 62, 127] {
+        let tuning = Meantone::quarter_comma(root);
+        assert_eq!(tuning.frequency(root), key_to_frequency_raw(root));
+        let (wolf_lo, wolf_hi) = tuning.wolf_interval();
+        assert_eq!((wolf_lo + 7) % 12, wolf_hi);
+        for k in 0..=127 {
+            let f = tuning.frequency(k);
+            assert_eq!(f, tuning.key_to_frequency(k));
+            let actual = 1200.0 * f32::log2(f / key_to_frequency_raw(k));
+            let deviation = tuning.deviation_from_et_cents(k % 12);
+            assert!(f32::abs(actual - deviation) < 0.01, "{} {}", k, actual);
+
+            // Major thirds from the root are pure.
+            if (k + 12 - root % 12) % 12 == 0 && k <= 123 {
+                let third = tuning.frequency(k + 4) / f;
+                assert!(f32::abs(third - 1.25) < 1.0e-6, "{} {}", k, third);
+            }
+            if k <= 120 {
+                let fifth = 1200.0 * f32::log2(tuning.frequency(k + 7) / f);
+                let expected = if k % 12 == wolf_lo { 737.64 } else { 696.58 };
+                assert!(f32::abs(fifth - expected) < 0.01, "{} {}", k, fifth);
+            }
+        }
+    }
+
+    // Third-comma meantone has pure minor thirds, and
+    // zero-comma meantone pure fifths.
+    let third_comma = Meantone::new(60, 1.0 / 3.0);
+    let minor_third = third_comma.frequency(63) / third_comma.frequency(60);
+    assert!(f32::abs(minor_third - 1.2) < 1.0e-6);
+    let pure = Meantone::new(60, 0.0);
+    for k in 53..=67 {
+        let fifth = pure.frequency(k + 7) / pure.frequency(k);
+        if k % 12 != 8 {
+            assert!(f32::abs(fifth - 1.5) < 1.0e-6, "{} {}", k, fifth);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "fraction must be finite")]
+fn test_meantone_invalid() {
+    Meantone::new(60, f32::NAN);
+}
+
+#[test]
+#[should_panic(expected = "pitch class 12 out of range 0..=11")]
+fn test_meantone_bad_pitch_class() {
+    Meantone::default().deviation_from_et_cents(12);
+}
+
+#[test]
+fn test_stretch_tuning() {
+    let tunings = [
+        StretchTuning::linear(0.0),
+        StretchTuning::linear(1.5),
+        StretchTuning::new(60, 4.0, 1.0, 3.0),
+        StretchTuning::RAILSBACK,
+    ];
+    for tuning in tunings {
+        let center = tuning.center();
+        assert_eq!(tuning.deviation_cents(center), 0.0);
+        assert_eq!(tuning.frequency(center), key_to_frequency_raw(center));
+        for k in 0..127 {
+            assert!(tuning.deviation_cents(k) <= tuning.deviation_cents(k + 1));
+            assert!(tuning.frequency(k) < tuning.frequency(k + 1), "{}", k);
+        }
+    }
+
+    let flat = StretchTuning::linear(0.0);
+    for k in 0..=127 {
+        assert_eq!(flat.frequency(k), key_to_frequency_raw(k));
+    }
+
+    // Linear stretch is antisymmetric about the center.
+    let linear = StretchTuning::linear(1.5);
+    for n in 1..=58 {
+        let up = linear.deviation_cents(69 + n);
+        assert_eq!(up, -linear.deviation_cents(69 - n));
+        assert!(
+            f32::abs(up - 1.5 * n as f32 / 12.0) < 1.0e-5,
+            "{} {}",
+            n,
+            up
+        );
+    }
+    let octave = linear.frequency(81) / linear.frequency(69);
+    assert!(f32::abs(1200.0 * f32::log2(octave) - 1201.5) < 0.01);
+
+    // The Railsback preset stretches the treble harder than
+    // the bass.
+    let railsback = StretchTuning::RAILSBACK;
+    assert_eq!(railsback.deviation_cents(21), -32.0);
+    assert_eq!(railsback.deviation_cents(57), -2.0);
+    assert_eq!(railsback.deviation_cents(81), 3.0);
+    let c8 = railsback.deviation_cents(108);
+    assert!(f32::abs(c8 - 3.0 * 3.25 * 3.25) < 1.0e-4, "{}", c8);
+    for n in 1..=48 {
+        let up = railsback.deviation_cents(69 + n);
+        let down = railsback.deviation_cents(69 - n);
+        assert!(f32::abs(up / down + 1.5) < 1.0e-5, "{} {} {}", n, up, down);
+    }
+}
+
+#[test]
+#[should_panic(expected = "treble stretch must be finite and non-negative")]
+fn test_stretch_tuning_invalid() {
+    StretchTuning::new(69, 1.0, -1.0, 1.0);
+}
+
+#[test]
+fn test_concert_pitch() {
+    let presets = [
+        (ConcertPitch::Baroque, 415.0),
+        (ConcertPitch::Classical, 430.0),
+        (ConcertPitch::A432, 432.0),
+        (ConcertPitch::Standard, 440.0),
+        (ConcertPitch::A442, 442.0),
+        (ConcertPitch::A444, 444.0),
+        (ConcertPitch::Custom(415.3), 415.3),
+        (ConcertPitch::Custom(1.0e-3), 1.0e-3),
+    ];
+    for (pitch, a4) in presets {
+        assert_eq!(pitch.a4_hz(), a4);
+        let tuning = Tuning::from(pitch);
+        assert_eq!(tuning.a4(), a4);
+        assert_eq!(tuning.frequency(69), a4);
+        assert_eq!(tuning.frequency(57), a4 / 2.0);
+        assert!(f32::abs(tuning.period(69) * a4 - 1.0) < 1.0e-6);
+        for k in 0..=127 {
+            let f = tuning.frequency(k);
+            assert_eq!(f, key_to_frequency_with_reference(k, a4));
+            let approx = tuning.frequency_approx(k);
+            assert!(f32::abs(approx - f) < 1.0e-4 * f, "{} {}", k, approx);
+            let p = tuning.period_approx(k);
+            assert!(f32::abs(p * f - 1.0) < 1.0e-4, "{} {}", k, p);
+        }
+    }
+    assert_eq!(ConcertPitch::default(), ConcertPitch::Standard);
+    assert_eq!(Tuning::from(ConcertPitch::default()), Tuning::default());
+    assert_eq!(
+        WerckmeisterIII::default().a4(),
+        ConcertPitch::Baroque.a4_hz()
+    );
+}
+
+#[test]
+#[should_panic(expected = "a4 must be finite and positive")]
+fn test_concert_pitch_invalid() {
+    let _ = Tuning::from(ConcertPitch::Custom(f32::NAN));
+}
+
+#[test]
+#[should_panic(expected = "reference frequency must be finite and positive")]
+fn test_edo_bad_reference() {
+    Edo::with_reference(19, 0, -1.0);
+}
@@ -0,0 +1,145 @@
+//! Typed frequency and period values.
+
+use core::fmt;
+use core::ops::{Div, Mul};
+
+use crate::{key_to_frequency, key_to_period};
+
+/// A frequency in Hz.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{Frequency, Period};
+/// let a4 = Frequency::from_key(69);
+/// assert_eq!(a4, Frequency(440.0));
+/// assert_eq!(a4 * 2.0, Frequency(880.0));
+/// let p: Period = a4.into();
+/// assert_eq!(p, Period(1.0 / 440.0));
+/// assert_eq!(a4.to_string(), "440 Hz");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Frequency(pub f32);
+
+/// A period in seconds.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::{Frequency, Period};
+/// let a4 = Period::from_key(69);
+/// assert_eq!(a4, Period(1.0 / 440.0));
+/// let f: Frequency = (a4 * 2.0).into();
+/// assert_eq!(f, Frequency(220.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Period(pub f32);
+
+impl Frequency {
+    /// Frequency of the given midi key. See
+    /// [key_to_frequency].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in the range `0..=127`.
+    pub fn from_key(key: u8) -> Self {
+        key_to_frequency(key)
+    }
+}
+
+impl Period {
+    /// Unit period of the given midi key. See
+    /// [key_to_period].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in the range `0..=127`.
+    pub fn from_key(key: u8) -> Self {
+        key_to_period(key)
+    }
+}
+
+impl From<Frequency> for Period {
+    fn from(f: Frequency) -> Self {
+        Period(1.0 / f.0)
+    }
+}
+
+impl From<Period> for Frequency {
+    fn from(p: Period) -> Self {
+        Frequency(1.0 / p.0)
+    }
+}
+
+impl Mul<f32> for Frequency {
+    type Output = Frequency;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Frequency(self.0 * rhs)
+    }
+}
+
+impl Div<f32> for Frequency {
+    type Output = Frequency;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        Frequency(self.0 / rhs)
+    }
+}
+
+impl Mul<f32> for Period {
+    type Output = Period;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Period(self.0 * rhs)
+    }
+}
+
+impl Div<f32> for Period {
+    type Output = Period;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        Period(self.0 / rhs)
+    }
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)?;
+        write!(f, " Hz")
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)?;
+        write!(f, " s")
+    }
+}
+
+#[test]
+fn test_units() {
+    use crate::{
+        key_to_frequency_approx, key_to_frequency_approx_raw, key_to_frequency_raw,
+        key_to_period_approx, key_to_period_approx_raw, key_to_period_raw,
+    };
+
+    for key in 0..=127 {
+        let f = Frequency::from_key(key);
+        let p = Period::from_key(key);
+        assert_eq!(f.0, key_to_frequency_raw(key));
+        assert_eq!(p.0, key_to_period_raw(key));
+        assert_eq!(
+            key_to_frequency_approx(key).0,
+            key_to_frequency_approx_raw(key)
+        );
+        assert_eq!(key_to_period_approx(key).0, key_to_period_approx_raw(key));
+        assert_eq!(Period::from(f), p);
+        let g = Frequency::from(p);
+        assert!(f32::abs(g.0 - f.0) < 1.0e-6 * f.0, "{} {}", f, g);
+    }
+    assert!(Frequency::from_key(60) < Frequency::from_key(61));
+    assert!(Period::from_key(60) > Period::from_key(61));
+    assert_eq!(Frequency(880.0) / 2.0, Frequency(440.0));
+    assert_eq!(Period(0.5) / 2.0, Period(0.25));
+}
@@ -0,0 +1,97 @@
+//! Wavelength and wavenumber of notes in air.
+
+use core::f32::consts::TAU;
+
+use crate::key_to_frequency_raw;
+
+/// Speed of sound in dry air at 20 °C, in meters per
+/// second.
+pub const SPEED_OF_SOUND: f32 = 343.0;
+
+/// Computes the wavelength in meters of the given midi key
+/// in a medium where sound travels at `speed_of_sound_m_s`
+/// meters per second, using the formula
+///    $$\lambda = \frac{v}{f_k}$$
+/// where $f_k$ is the frequency of the key.
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_wavelength;
+/// assert_eq!(key_to_wavelength(69, 440.0), 1.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_wavelength(key: u8, speed_of_sound_m_s: f32) -> f32 {
+    speed_of_sound_m_s / key_to_frequency_raw(key)
+}
+
+/// Like [key_to_wavelength], in air at 20 °C where sound
+/// travels at [SPEED_OF_SOUND].
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_wavelength_default;
+/// // A4 is about 78 cm long.
+/// assert_eq!((key_to_wavelength_default(69) * 100.0).round(), 78.0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_wavelength_default(key: u8) -> f32 {
+    key_to_wavelength(key, SPEED_OF_SOUND)
+}
+
+/// Computes the angular wavenumber in radians per meter of
+/// the given midi key in a medium where sound travels at
+/// `speed_of_sound_m_s` meters per second, using the
+/// formula
+///    $$k = \frac{2 \pi f_k}{v}$$
+/// where $f_k$ is the frequency of the key. This is $2 \pi$
+/// over the [wavelength](key_to_wavelength).
+///
+/// # Examples
+///
+/// ```
+/// # use keytones::key_to_wavenumber;
+/// use core::f32::consts::TAU;
+/// assert_eq!(key_to_wavenumber(69, 440.0), TAU);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `key` is not in the range `0..=127`.
+pub fn key_to_wavenumber(key: u8, speed_of_sound_m_s: f32) -> f32 {
+    TAU * key_to_frequency_raw(key) / speed_of_sound_m_s
+}
+
+#[test]
+fn test_wave_quantities() {
+    for k in 0..=127 {
+        let f = key_to_frequency_raw(k);
+        let lambda = key_to_wavelength_default(k);
+        assert_eq!(lambda, key_to_wavelength(k, SPEED_OF_SOUND));
+        assert!(f32::abs(lambda * f - SPEED_OF_SOUND) < 1.0e-4 * SPEED_OF_SOUND);
+        let wavenumber = key_to_wavenumber(k, SPEED_OF_SOUND);
+        assert!(f32::abs(wavenumber * lambda - TAU) < 1.0e-5, "{}", k);
+        if k >= 12 {
+            let octave_down = key_to_wavelength_default(k - 12);
+            assert!(f32::abs(octave_down - 2.0 * lambda) < 1.0e-5 * lambda);
+        }
+    }
+    // The 16 Hz rumble of C0 is over 20 m long.
+    assert!(key_to_wavelength_default(12) > 20.0);
+    // In water sound travels about 4.3 times faster.
+    let ratio = key_to_wavelength(69, 1481.0) / key_to_wavelength_default(69);
+    assert!(f32::abs(ratio - 1481.0 / 343.0) < 1.0e-5);
+}
+
+#[test]
+#[should_panic(expected = "key 128 out of range 0..=127")]
+fn test_wave_out_of_range() {
+    key_to_wavelength_default(128);
+}